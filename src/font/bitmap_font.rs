@@ -0,0 +1,532 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    prelude::*,
+    primitives::Rectangle,
+    text::{CharacterStyle, DecorationColor, TextMetrics, TextRenderer, VerticalAlignment},
+};
+
+const PSF1_MAGIC: [u8; 2] = [0x36, 0x04];
+const PSF1_MODE512: u8 = 0x01;
+const PSF1_MODE_HAS_TAB: u8 = 0x02;
+const PSF1_MODE_HAS_SEQ: u8 = 0x04;
+
+const PSF2_MAGIC: [u8; 4] = [0x72, 0xb5, 0x4a, 0x86];
+const PSF2_HAS_UNICODE_TABLE: u32 = 0x01;
+
+/// A no-antialiasing, pixel-exact bitmap font backend, parsed from a PC
+/// Screen Font (PSF v1 or v2) file.
+///
+/// Unlike [`Font`](crate::font::Font), which rasterizes vector outlines,
+/// `BitmapFont` just looks a glyph's fixed-size bitmap up and blits its set
+/// bits, making it a cheap, allocation-free, deterministic alternative for
+/// fixed-cell terminal-style UIs on constrained targets. `BitmapFont` plays
+/// the combined role [`Font`](crate::font::Font) and
+/// [`FontTextStyle`](crate::font::FontTextStyle) split in two, implementing
+/// `CharacterStyle`/`TextRenderer` directly.
+pub struct BitmapFont<C: PixelColor> {
+    glyph_width: u32,
+    glyph_height: u32,
+    bytes_per_row: usize,
+    glyph_count: usize,
+    glyph_data: Vec<u8>,
+    /// Maps a Unicode scalar value to a glyph index, for PSF1 or PSF2 fonts
+    /// with a unicode translation table. `None` means glyph index == code point,
+    /// as is conventional for PSF1 fonts without one (typically CP437).
+    unicode_map: Option<HashMap<char, usize>>,
+
+    /// Text color.
+    pub text_color: Option<C>,
+
+    /// Background color.
+    pub background_color: Option<C>,
+
+    /// Underline color.
+    pub underline_color: DecorationColor<C>,
+
+    /// Strikethrough color.
+    pub strikethrough_color: DecorationColor<C>,
+
+    /// Integer scale factor each glyph cell is blown up by.
+    pub scale: u32,
+
+    _c: PhantomData<C>,
+}
+
+impl<C: PixelColor> BitmapFont<C> {
+    /// Parses a PC Screen Font (PSF v1 or v2) file.
+    pub fn from_psf(bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() >= 2 && bytes[0..2] == PSF1_MAGIC {
+            Self::from_psf1(bytes)
+        } else if bytes.len() >= 4 && bytes[0..4] == PSF2_MAGIC {
+            Self::from_psf2(bytes)
+        } else {
+            Err("Not a PSF v1 or v2 font file")
+        }
+    }
+
+    fn from_psf1(bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() < 4 {
+            return Err("PSF1 header is truncated");
+        }
+
+        let mode = bytes[2];
+        let charsize = bytes[3] as usize;
+        let glyph_count = if mode & PSF1_MODE512 != 0 { 512 } else { 256 };
+
+        let glyph_data_len = glyph_count * charsize;
+        let glyph_data = bytes
+            .get(4..4 + glyph_data_len)
+            .ok_or("PSF1 glyph data is truncated")?
+            .to_vec();
+
+        let has_unicode_table = mode & (PSF1_MODE_HAS_TAB | PSF1_MODE_HAS_SEQ) != 0;
+        let unicode_map = if has_unicode_table {
+            Some(parse_psf1_unicode_table(&bytes[4 + glyph_data_len..]))
+        } else {
+            None
+        };
+
+        Ok(BitmapFont {
+            glyph_width: 8,
+            glyph_height: charsize as u32,
+            bytes_per_row: 1,
+            glyph_count,
+            glyph_data,
+            unicode_map,
+            text_color: None,
+            background_color: None,
+            underline_color: DecorationColor::None,
+            strikethrough_color: DecorationColor::None,
+            scale: 1,
+            _c: PhantomData,
+        })
+    }
+
+    fn from_psf2(bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() < 32 {
+            return Err("PSF2 header is truncated");
+        }
+
+        let read_u32 = |offset: usize| {
+            u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+        };
+
+        let header_size = read_u32(8) as usize;
+        let flags = read_u32(12);
+        let glyph_count = read_u32(16) as usize;
+        let bytes_per_glyph = read_u32(20) as usize;
+        let glyph_height = read_u32(24);
+        let glyph_width = read_u32(28);
+
+        let glyph_data_start = header_size;
+        let glyph_data_len = glyph_count * bytes_per_glyph;
+        let glyph_data = bytes
+            .get(glyph_data_start..glyph_data_start + glyph_data_len)
+            .ok_or("PSF2 glyph data is truncated")?
+            .to_vec();
+
+        let unicode_map = if flags & PSF2_HAS_UNICODE_TABLE != 0 {
+            let table_start = glyph_data_start + glyph_data_len;
+            Some(parse_psf2_unicode_table(&bytes[table_start..]))
+        } else {
+            None
+        };
+
+        Ok(BitmapFont {
+            glyph_width,
+            glyph_height,
+            bytes_per_row: bytes_per_glyph / glyph_height.max(1) as usize,
+            glyph_count,
+            glyph_data,
+            unicode_map,
+            text_color: None,
+            background_color: None,
+            underline_color: DecorationColor::None,
+            strikethrough_color: DecorationColor::None,
+            scale: 1,
+            _c: PhantomData,
+        })
+    }
+
+    /// Sets the integer scale factor glyph cells are blown up by.
+    pub fn scale(mut self, scale: u32) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Sets the text color.
+    pub fn text_color(mut self, text_color: C) -> Self {
+        self.text_color = Some(text_color);
+        self
+    }
+
+    /// Sets the background color.
+    pub fn background_color(mut self, background_color: C) -> Self {
+        self.background_color = Some(background_color);
+        self
+    }
+
+    fn glyph_index(&self, ch: char) -> Option<usize> {
+        let index = match &self.unicode_map {
+            Some(map) => map.get(&ch).copied()?,
+            None => ch as usize,
+        };
+
+        (index < self.glyph_count).then_some(index)
+    }
+
+    /// Returns glyph `index`'s bitmap rows, or `None` if `index` is out of
+    /// range. A unicode table built from a malformed/oversized font file can
+    /// otherwise produce an index past `glyph_count`.
+    fn glyph_bitmap(&self, index: usize) -> Option<&[u8]> {
+        if index >= self.glyph_count {
+            return None;
+        }
+
+        let glyph_len = self.bytes_per_row * self.glyph_height as usize;
+        let start = index * glyph_len;
+
+        self.glyph_data.get(start..start + glyph_len)
+    }
+
+    fn resolve_decoration_color(&self, color: DecorationColor<C>) -> Option<C> {
+        match color {
+            DecorationColor::None => None,
+            DecorationColor::TextColor => self.text_color,
+            DecorationColor::Custom(c) => Some(c),
+        }
+    }
+
+    fn cell_size(&self) -> Size {
+        Size::new(self.glyph_width * self.scale, self.glyph_height * self.scale)
+    }
+
+    fn draw_decorations<D>(&self, width: u32, position: Point, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        if let Some(color) = self.resolve_decoration_color(self.strikethrough_color) {
+            let y = (self.glyph_height * self.scale / 2) as i32;
+            target.fill_solid(
+                &Rectangle::new(position + Point::new(0, y), Size::new(width, self.scale)),
+                color,
+            )?;
+        }
+
+        if let Some(color) = self.resolve_decoration_color(self.underline_color) {
+            let y = (self.glyph_height * self.scale).saturating_sub(self.scale) as i32;
+            target.fill_solid(
+                &Rectangle::new(position + Point::new(0, y), Size::new(width, self.scale)),
+                color,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Draws a single glyph cell (background, set bits, decorations) and
+    /// returns the position advanced past it.
+    fn draw_glyph<D>(&self, ch: char, position: Point, target: &mut D) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let cell = Rectangle::new(position, self.cell_size());
+
+        if let Some(background_color) = self.background_color {
+            target.fill_solid(&cell, background_color)?;
+        }
+
+        if let (Some(bitmap), Some(text_color)) = (
+            self.glyph_index(ch).and_then(|index| self.glyph_bitmap(index)),
+            self.text_color,
+        ) {
+            for row in 0..self.glyph_height {
+                for col in 0..self.glyph_width {
+                    let byte = bitmap[row as usize * self.bytes_per_row + (col / 8) as usize];
+                    let bit = (byte >> (7 - col % 8)) & 1;
+
+                    if bit != 0 {
+                        let block = Rectangle::new(
+                            position + Point::new((col * self.scale) as i32, (row * self.scale) as i32),
+                            Size::new(self.scale, self.scale),
+                        );
+                        target.fill_solid(&block, text_color)?;
+                    }
+                }
+            }
+        }
+
+        self.draw_decorations(cell.size.width, position, target)?;
+
+        Ok(position + Point::new(cell.size.width as i32, 0))
+    }
+}
+
+impl<C: PixelColor> CharacterStyle for BitmapFont<C> {
+    type Color = C;
+
+    fn set_text_color(&mut self, text_color: Option<Self::Color>) {
+        self.text_color = text_color;
+    }
+
+    fn set_background_color(&mut self, background_color: Option<Self::Color>) {
+        self.background_color = background_color;
+    }
+
+    fn set_underline_color(&mut self, underline_color: DecorationColor<Self::Color>) {
+        self.underline_color = underline_color;
+    }
+
+    fn set_strikethrough_color(&mut self, strikethrough_color: DecorationColor<Self::Color>) {
+        self.strikethrough_color = strikethrough_color;
+    }
+}
+
+impl<C: PixelColor> TextRenderer for BitmapFont<C> {
+    type Color = C;
+
+    fn draw_string<D>(&self, text: &str, position: Point, target: &mut D) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let mut p = position;
+
+        for ch in text.chars() {
+            p = self.draw_glyph(ch, p, target)?;
+        }
+
+        Ok(p)
+    }
+
+    fn draw_whitespace<D>(
+        &self,
+        width: u32,
+        position: Point,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        if let Some(background_color) = self.background_color {
+            target.fill_solid(
+                &Rectangle::new(position, Size::new(width, self.glyph_height * self.scale)),
+                background_color,
+            )?;
+        }
+
+        self.draw_decorations(width, position, target)?;
+
+        Ok(position + Point::new(width as i32, 0))
+    }
+
+    fn measure_string(&self, text: &str, position: Point) -> TextMetrics {
+        let width = text.chars().count() as u32 * self.glyph_width * self.scale;
+        let size = Size::new(width, self.glyph_height * self.scale);
+
+        TextMetrics {
+            bounding_box: Rectangle::new(position, size),
+            next_position: position + size.x_axis(),
+        }
+    }
+
+    fn vertical_offset(&self, position: Point, vertical_alignment: VerticalAlignment) -> Point {
+        let cell_height = (self.glyph_height * self.scale) as i32;
+
+        let y_offset = match vertical_alignment {
+            VerticalAlignment::Top => 0,
+            VerticalAlignment::Bottom | VerticalAlignment::Alphabetic => -cell_height,
+            VerticalAlignment::Middle => -cell_height / 2,
+        };
+
+        Point::new(position.x, position.y + y_offset)
+    }
+
+    fn line_height(&self) -> u32 {
+        self.glyph_height * self.scale
+    }
+}
+
+/// Parses a PSF1 "screen map"/Unicode table: for each glyph in order, a
+/// sequence of UTF-16-ish 2-byte character codes terminated by `0xFFFF`,
+/// each mapping that code point to the current glyph index.
+fn parse_psf1_unicode_table(mut table: &[u8]) -> HashMap<char, usize> {
+    let mut map = HashMap::new();
+    let mut glyph_index = 0;
+
+    while table.len() >= 2 {
+        let code = u16::from_le_bytes([table[0], table[1]]);
+        table = &table[2..];
+
+        if code == 0xFFFF {
+            glyph_index += 1;
+            continue;
+        }
+
+        if let Some(ch) = char::from_u32(code as u32) {
+            map.entry(ch).or_insert(glyph_index);
+        }
+    }
+
+    map
+}
+
+/// Parses a PSF2 Unicode table: for each glyph in order, a sequence of
+/// UTF-8 encoded code points terminated by `0xFF`.
+fn parse_psf2_unicode_table(mut table: &[u8]) -> HashMap<char, usize> {
+    let mut map = HashMap::new();
+    let mut glyph_index = 0;
+
+    while !table.is_empty() {
+        // `0xFF` never appears inside a UTF-8 encoded code point, so it's
+        // safe to find the next entry's end before validating UTF-8 on just
+        // that bounded slice (validating the whole remaining `table` would
+        // always fail, since it still contains later `0xFF` separators).
+        let end = table.iter().position(|&b| b == 0xFF).unwrap_or(table.len());
+        let entry = &table[..end];
+
+        if let Ok(s) = std::str::from_utf8(entry) {
+            for ch in s.chars() {
+                map.entry(ch).or_insert(glyph_index);
+            }
+        }
+
+        table = table.get(end + 1..).unwrap_or(&[]);
+        glyph_index += 1;
+    }
+
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics_core::pixelcolor::BinaryColor;
+
+    fn psf1_header(mode: u8, charsize: u8) -> Vec<u8> {
+        vec![PSF1_MAGIC[0], PSF1_MAGIC[1], mode, charsize]
+    }
+
+    #[test]
+    fn from_psf_rejects_unknown_magic() {
+        assert!(BitmapFont::<BinaryColor>::from_psf(&[0, 0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn from_psf1_truncated_header_errors() {
+        assert!(BitmapFont::<BinaryColor>::from_psf(&PSF1_MAGIC).is_err());
+    }
+
+    #[test]
+    fn from_psf1_truncated_glyph_data_errors() {
+        let mut bytes = psf1_header(0, 16);
+        bytes.extend(std::iter::repeat(0).take(4));
+
+        assert!(BitmapFont::<BinaryColor>::from_psf(&bytes).is_err());
+    }
+
+    #[test]
+    fn from_psf1_parses_256_glyph_font() {
+        let charsize = 8u8;
+        let mut bytes = psf1_header(0, charsize);
+        bytes.extend(std::iter::repeat(0xAA).take(256 * charsize as usize));
+
+        let font = BitmapFont::<BinaryColor>::from_psf(&bytes).unwrap();
+
+        assert_eq!(font.glyph_width, 8);
+        assert_eq!(font.glyph_height, charsize as u32);
+        assert_eq!(font.glyph_count, 256);
+        assert!(font.unicode_map.is_none());
+    }
+
+    #[test]
+    fn from_psf1_mode512_doubles_glyph_count() {
+        let charsize = 8u8;
+        let mut bytes = psf1_header(PSF1_MODE512, charsize);
+        bytes.extend(std::iter::repeat(0).take(512 * charsize as usize));
+
+        let font = BitmapFont::<BinaryColor>::from_psf(&bytes).unwrap();
+
+        assert_eq!(font.glyph_count, 512);
+    }
+
+    #[test]
+    fn from_psf2_truncated_header_errors() {
+        assert!(BitmapFont::<BinaryColor>::from_psf(&PSF2_MAGIC).is_err());
+    }
+
+    fn psf2_header(glyph_count: u32, bytes_per_glyph: u32, height: u32, width: u32, flags: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&PSF2_MAGIC);
+        bytes.extend_from_slice(&0u32.to_le_bytes()); // version
+        bytes.extend_from_slice(&32u32.to_le_bytes()); // header_size
+        bytes.extend_from_slice(&flags.to_le_bytes());
+        bytes.extend_from_slice(&glyph_count.to_le_bytes());
+        bytes.extend_from_slice(&bytes_per_glyph.to_le_bytes());
+        bytes.extend_from_slice(&height.to_le_bytes());
+        bytes.extend_from_slice(&width.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn from_psf2_truncated_glyph_data_errors() {
+        let bytes = psf2_header(1, 16, 16, 8, 0);
+
+        assert!(BitmapFont::<BinaryColor>::from_psf(&bytes).is_err());
+    }
+
+    #[test]
+    fn from_psf2_parses_glyph_dimensions() {
+        let mut bytes = psf2_header(2, 16, 16, 8, 0);
+        bytes.extend(std::iter::repeat(0).take(2 * 16));
+
+        let font = BitmapFont::<BinaryColor>::from_psf(&bytes).unwrap();
+
+        assert_eq!(font.glyph_width, 8);
+        assert_eq!(font.glyph_height, 16);
+        assert_eq!(font.glyph_count, 2);
+        assert!(font.unicode_map.is_none());
+    }
+
+    #[test]
+    fn glyph_bitmap_rejects_out_of_range_index() {
+        let mut bytes = psf1_header(0, 8);
+        bytes.extend(std::iter::repeat(0).take(256 * 8));
+        let font = BitmapFont::<BinaryColor>::from_psf(&bytes).unwrap();
+
+        assert!(font.glyph_bitmap(256).is_none());
+        assert!(font.glyph_bitmap(0).is_some());
+    }
+
+    #[test]
+    fn parse_psf1_unicode_table_maps_code_points_to_glyph_index() {
+        let mut table = Vec::new();
+        // glyph 0 maps to 'A'
+        table.extend_from_slice(&(b'A' as u16).to_le_bytes());
+        table.extend_from_slice(&0xFFFFu16.to_le_bytes());
+        // glyph 1 maps to 'B'
+        table.extend_from_slice(&(b'B' as u16).to_le_bytes());
+        table.extend_from_slice(&0xFFFFu16.to_le_bytes());
+
+        let map = parse_psf1_unicode_table(&table);
+
+        assert_eq!(map.get(&'A'), Some(&0));
+        assert_eq!(map.get(&'B'), Some(&1));
+    }
+
+    #[test]
+    fn parse_psf2_unicode_table_maps_utf8_code_points_to_glyph_index() {
+        let mut table = Vec::new();
+        table.extend_from_slice("A".as_bytes());
+        table.push(0xFF);
+        table.extend_from_slice("é".as_bytes());
+        table.push(0xFF);
+
+        let map = parse_psf2_unicode_table(&table);
+
+        assert_eq!(map.get(&'A'), Some(&0));
+        assert_eq!(map.get(&'é'), Some(&1));
+    }
+}
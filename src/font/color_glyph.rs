@@ -0,0 +1,156 @@
+use tiny_skia::{Color, FillRule, Paint, PathBuilder, Pixmap, Transform};
+use ttf_parser::{Face, GlyphId, RasterImageFormat};
+
+/// A decoded color glyph bitmap, ready to be scaled and blitted.
+pub struct ColorGlyphImage {
+    /// The glyph's bitmap, decoded to premultiplied RGBA.
+    pub pixmap: Pixmap,
+
+    /// The `pixels_per_em` the bitmap was authored at; scale by
+    /// `font_size / pixels_per_em` when blitting at a different size.
+    pub pixels_per_em: u16,
+}
+
+/// Reads a color glyph — an embedded bitmap (`sbix`/`CBDT`/`CBLC`) or a
+/// layered `COLR`/`CPAL` vector glyph, as used by color emoji and multicolor
+/// icon fonts — and decodes/composites it to a single RGBA bitmap.
+///
+/// `text_color` fills any `COLR` layer flagged to use the current
+/// foreground color instead of a `CPAL` palette entry. Returns `None` for
+/// glyphs with no color data (i.e. almost every glyph in an ordinary vector
+/// font), or for a raster format this crate doesn't decode.
+pub fn color_glyph_image(face: &Face, glyph_id: GlyphId, text_color: Color) -> Option<ColorGlyphImage> {
+    raster_glyph_image(face, glyph_id).or_else(|| colr_glyph_image(face, glyph_id, text_color))
+}
+
+/// Reads a glyph's embedded color bitmap (the `sbix` or `CBDT`/`CBLC`
+/// tables).
+fn raster_glyph_image(face: &Face, glyph_id: GlyphId) -> Option<ColorGlyphImage> {
+    let raster = face.glyph_raster_image(glyph_id, u16::MAX)?;
+
+    if raster.format != RasterImageFormat::PNG {
+        return None;
+    }
+
+    let decoded = image::load_from_memory(raster.data).ok()?;
+    let rgba = decoded.to_rgba8();
+    let (width, height) = rgba.dimensions();
+
+    let mut pixmap = Pixmap::new(width, height)?;
+
+    for (src, dst) in rgba.pixels().zip(pixmap.pixels_mut().iter_mut()) {
+        let [r, g, b, a] = src.0;
+        *dst = tiny_skia::PremultipliedColorU8::from_rgba(
+            (r as u16 * a as u16 / 255) as u8,
+            (g as u16 * a as u16 / 255) as u8,
+            (b as u16 * a as u16 / 255) as u8,
+            a,
+        )?;
+    }
+
+    let pixels_per_em = if raster.pixels_per_em == 0 {
+        height as u16
+    } else {
+        raster.pixels_per_em
+    };
+
+    Some(ColorGlyphImage {
+        pixmap,
+        pixels_per_em,
+    })
+}
+
+/// Composites a glyph's layered `COLR` outline, filling each layer with its
+/// resolved `CPAL` palette 0 color (a layer whose palette index is `0xFFFF`
+/// uses `text_color` instead, per the `COLR` spec's "current color"
+/// convention), into a single RGBA bitmap at the font's native unit scale
+/// (`pixels_per_em == units_per_em`).
+fn colr_glyph_image(face: &Face, glyph_id: GlyphId, text_color: Color) -> Option<ColorGlyphImage> {
+    let colr = face.tables().colr?;
+    let cpal = face.tables().cpal?;
+    let layers: Vec<(GlyphId, u16)> = colr.get(glyph_id)?.collect();
+
+    if layers.is_empty() {
+        return None;
+    }
+
+    let mut min_x = f32::MAX;
+    let mut min_y = f32::MAX;
+    let mut max_x = f32::MIN;
+    let mut max_y = f32::MIN;
+
+    let mut layer_paths = Vec::with_capacity(layers.len());
+    for (layer_glyph_id, palette_index) in layers {
+        let mut tracer = OutlineTracer::default();
+        let bbox = face.outline_glyph(layer_glyph_id, &mut tracer)?;
+        let path = tracer.path_builder.finish()?;
+
+        min_x = min_x.min(bbox.x_min as f32);
+        min_y = min_y.min(bbox.y_min as f32);
+        max_x = max_x.max(bbox.x_max as f32);
+        max_y = max_y.max(bbox.y_max as f32);
+
+        let color = if palette_index == 0xFFFF {
+            text_color
+        } else {
+            match cpal.get(0, palette_index) {
+                Some(c) => Color::from_rgba8(c.red, c.green, c.blue, c.alpha),
+                None => text_color,
+            }
+        };
+
+        layer_paths.push((path, color));
+    }
+
+    let width = (max_x - min_x).ceil().max(1.0) as u32;
+    let height = (max_y - min_y).ceil().max(1.0) as u32;
+
+    let mut pixmap = Pixmap::new(width, height)?;
+
+    // Font outlines are y-up, in font units with the origin at the glyph's
+    // own origin; flip y and translate so the bitmap's top-left is (0, 0).
+    let transform = Transform::from_row(1.0, 0.0, 0.0, -1.0, -min_x, max_y);
+
+    for (path, color) in &layer_paths {
+        let mut paint = Paint::default();
+        paint.anti_alias = true;
+        paint.set_color(*color);
+
+        pixmap.fill_path(path, &paint, FillRule::Winding, transform, None);
+    }
+
+    Some(ColorGlyphImage {
+        pixmap,
+        pixels_per_em: face.units_per_em() as u16,
+    })
+}
+
+/// Traces a `ttf_parser` glyph outline (in raw font units) into a tiny-skia
+/// [`PathBuilder`], with no offset or scale applied — the caller is
+/// responsible for transforming font units to pixels.
+#[derive(Default)]
+struct OutlineTracer {
+    path_builder: PathBuilder,
+}
+
+impl ttf_parser::OutlineBuilder for OutlineTracer {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.path_builder.move_to(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.path_builder.line_to(x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.path_builder.quad_to(x1, y1, x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.path_builder.cubic_to(x1, y1, x2, y2, x, y);
+    }
+
+    fn close(&mut self) {
+        self.path_builder.close();
+    }
+}
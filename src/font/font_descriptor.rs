@@ -0,0 +1,139 @@
+use embedded_graphics_core::pixelcolor::PixelColor;
+
+use crate::font::FontStyle;
+
+/// A font weight, from `Thin` to `Black`.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum FontWeight {
+    Thin,
+    ExtraLight,
+    Light,
+    #[default]
+    Normal,
+    Medium,
+    SemiBold,
+    Bold,
+    ExtraBold,
+    Black,
+}
+
+/// A font slant.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum FontSlant {
+    #[default]
+    Normal,
+    Italic,
+}
+
+/// Describes a specific face — family, pixel size, weight and slant — in
+/// one value, the way higher-level toolkits describe fonts, instead of
+/// callers juggling a separate registered family name per weight/style
+/// combination.
+///
+/// A descriptor resolves to concrete glyph data at draw time by looking up
+/// [`Self::registry_key`] in a display's font registry, so a `Bold`/`Italic`
+/// variant must be registered (via
+/// [`TinySkiaDisplay::register_font`](crate::TinySkiaDisplay::register_font))
+/// under that key, e.g. `"Roboto-Bold-Italic"`. The plain `Normal`/`Normal`
+/// combination resolves to the family name itself, so existing
+/// single-weight registrations keep working unchanged.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct FontDescriptor {
+    /// Font family name.
+    pub family: String,
+
+    /// Text pixel size.
+    pub pixel_size: u32,
+
+    /// Font weight.
+    pub weight: FontWeight,
+
+    /// Font slant.
+    pub slant: FontSlant,
+}
+
+impl FontDescriptor {
+    /// Creates a descriptor for the `Normal` weight and slant of `family`
+    /// at `pixel_size`.
+    pub fn new(family: impl Into<String>, pixel_size: u32) -> Self {
+        Self {
+            family: family.into(),
+            pixel_size,
+            weight: FontWeight::Normal,
+            slant: FontSlant::Normal,
+        }
+    }
+
+    /// Sets the font weight.
+    pub fn weight(mut self, weight: FontWeight) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Sets the font slant.
+    pub fn slant(mut self, slant: FontSlant) -> Self {
+        self.slant = slant;
+        self
+    }
+
+    /// Shorthand for `.weight(FontWeight::Bold)`.
+    pub fn bold(self) -> Self {
+        self.weight(FontWeight::Bold)
+    }
+
+    /// Shorthand for `.slant(FontSlant::Italic)`.
+    pub fn italic(self) -> Self {
+        self.slant(FontSlant::Italic)
+    }
+
+    /// The font registry key this descriptor resolves to: the family name
+    /// alone for the `Normal`/`Normal` combination, or `"{family}-{weight}-{slant}"`
+    /// otherwise.
+    pub fn registry_key(&self) -> String {
+        match (self.weight, self.slant) {
+            (FontWeight::Normal, FontSlant::Normal) => self.family.clone(),
+            (weight, slant) => format!("{}-{:?}-{:?}", self.family, weight, slant),
+        }
+    }
+
+    /// Resolves this descriptor to a [`FontStyle`] naming
+    /// [`Self::registry_key`], ready to be looked up in a display's font
+    /// registry via [`TinySkiaDisplay::text_style`](crate::TinySkiaDisplay::text_style).
+    pub fn into_style<C: PixelColor>(self, text_color: C) -> FontStyle<C> {
+        FontStyle::new(self.registry_key(), text_color, self.pixel_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normal_weight_and_slant_resolve_to_the_family_name() {
+        assert_eq!(FontDescriptor::new("Roboto", 12).registry_key(), "Roboto");
+    }
+
+    #[test]
+    fn bold_resolves_to_a_weight_suffixed_key() {
+        assert_eq!(
+            FontDescriptor::new("Roboto", 12).bold().registry_key(),
+            "Roboto-Bold-Normal"
+        );
+    }
+
+    #[test]
+    fn italic_resolves_to_a_slant_suffixed_key() {
+        assert_eq!(
+            FontDescriptor::new("Roboto", 12).italic().registry_key(),
+            "Roboto-Normal-Italic"
+        );
+    }
+
+    #[test]
+    fn bold_italic_resolves_to_both_suffixes() {
+        assert_eq!(
+            FontDescriptor::new("Roboto", 12).bold().italic().registry_key(),
+            "Roboto-Bold-Italic"
+        );
+    }
+}
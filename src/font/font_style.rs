@@ -1,4 +1,27 @@
-use embedded_graphics::pixelcolor::PixelColor;
+use std::sync::Arc;
+
+use embedded_graphics_core::pixelcolor::PixelColor;
+
+/// Where a [`FontStyle`] gets its glyph data from.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum FontSource {
+    /// Looked up by family name in a
+    /// [`TinySkiaDisplay`](crate::TinySkiaDisplay)'s font registry, via
+    /// [`TinySkiaDisplay::register_font`](crate::TinySkiaDisplay::register_font).
+    Registered(String),
+
+    /// Raw TrueType/OpenType font bytes, rasterized directly at
+    /// [`FontStyle::pixel_size`] without needing the font to be registered
+    /// first. Resolved fresh on every draw, so prefer `Registered` for text
+    /// that's redrawn often.
+    Inline(Arc<[u8]>),
+}
+
+impl Default for FontSource {
+    fn default() -> Self {
+        FontSource::Registered(String::new())
+    }
+}
 
 /// Style properties for font text.
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
@@ -7,12 +30,17 @@ pub struct FontStyle<C>
 where
     C: PixelColor,
 {
-    /// Font family.
-    pub font_family: String,
+    /// Where this style's glyph data comes from.
+    pub font_source: FontSource,
 
     /// Text color.
     pub text_color: Option<C>,
 
+    /// Background color. When set, the text's bounding box is filled with
+    /// this color before glyph coverage is drawn on top, useful for
+    /// selected/highlighted text or terminal-style cells.
+    pub background_color: Option<C>,
+
     /// Text pixel size.
     pub pixel_size: u32,
 }
@@ -21,11 +49,25 @@ impl<C> FontStyle<C>
 where
     C: PixelColor,
 {
-    /// Creates a font style.
+    /// Creates a font style that looks its font up by family name in a
+    /// display's font registry.
     pub fn new(font_family: impl Into<String>, text_color: C, pixel_size: u32) -> Self {
         Self {
-            font_family: font_family.into(),
+            font_source: FontSource::Registered(font_family.into()),
             text_color: Some(text_color),
+            background_color: None,
+            pixel_size,
+        }
+    }
+
+    /// Creates a font style from inline TrueType/OpenType font bytes,
+    /// rasterized directly at `pixel_size` without needing the font to be
+    /// registered first.
+    pub fn from_ttf(font_bytes: impl Into<Vec<u8>>, text_color: C, pixel_size: u32) -> Self {
+        Self {
+            font_source: FontSource::Inline(Arc::from(font_bytes.into())),
+            text_color: Some(text_color),
+            background_color: None,
             pixel_size,
         }
     }
@@ -44,12 +86,28 @@ impl<C> FontStyleBuilder<C>
 where
     C: PixelColor,
 {
-    /// Creates a new text style builder with a given font.
+    /// Creates a new text style builder for a font looked up by family name
+    /// in a display's font registry.
     pub fn new(font_family: impl Into<String>) -> Self {
         Self {
             style: FontStyle {
-                font_family: font_family.into(),
+                font_source: FontSource::Registered(font_family.into()),
                 text_color: None,
+                background_color: None,
+                pixel_size: 0,
+            },
+        }
+    }
+
+    /// Creates a new text style builder for inline TrueType/OpenType font
+    /// bytes, rasterized directly without needing the font to be registered
+    /// first.
+    pub fn from_ttf(font_bytes: impl Into<Vec<u8>>) -> Self {
+        Self {
+            style: FontStyle {
+                font_source: FontSource::Inline(Arc::from(font_bytes.into())),
+                text_color: None,
+                background_color: None,
                 pixel_size: 0,
             },
         }
@@ -61,6 +119,12 @@ where
         self
     }
 
+    /// Sets the background color.
+    pub fn background_color(mut self, background_color: C) -> Self {
+        self.style.background_color = Some(background_color);
+        self
+    }
+
     /// Sets the pixel size.
     pub fn pixel_size(mut self, pixel_size: u32) -> Self {
         self.style.pixel_size = pixel_size;
@@ -76,15 +140,16 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use embedded_graphics::pixelcolor::BinaryColor;
+    use embedded_graphics_core::pixelcolor::BinaryColor;
 
     #[test]
     fn builder_default() {
         assert_eq!(
             FontStyleBuilder::<BinaryColor>::new("my_font").build(),
             FontStyle {
-                font_family: String::from("my_font"),
+                font_source: FontSource::Registered(String::from("my_font")),
                 text_color: None,
+                background_color: None,
                 pixel_size: 0
             }
         );
@@ -116,4 +181,35 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn builder_from_ttf() {
+        assert_eq!(
+            FontStyleBuilder::<BinaryColor>::from_ttf(vec![1, 2, 3])
+                .pixel_size(16)
+                .build(),
+            FontStyle {
+                font_source: FontSource::Inline(Arc::from(vec![1u8, 2, 3])),
+                text_color: None,
+                background_color: None,
+                pixel_size: 16
+            }
+        );
+    }
+
+    #[test]
+    fn builder_background_color() {
+        assert_eq!(
+            FontStyleBuilder::new("my_font")
+                .background_color(BinaryColor::On)
+                .build(),
+            {
+                let mut style = FontStyleBuilder::<BinaryColor>::new("my_font").build();
+
+                style.background_color = Some(BinaryColor::On);
+
+                style
+            }
+        );
+    }
 }
@@ -1,66 +1,350 @@
-use embedded_graphics::{
-    drawable::Drawable, geometry::Point, pixelcolor::PixelColor, style::Styled,
-    transform::Transform,
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Point, Size},
+    pixelcolor::PixelColor,
+    prelude::*,
+    primitives::Rectangle,
+    text::TextRenderer,
+    Pixel,
 };
 
-use crate::{font::FontStyle, TinySkiaDisplay};
+use crate::font::rotation::PixelBuffer;
+use crate::font::{layout_lines, layout_lines_with_mode, FontStyle, FontTextStyle, Rotation, WrapMode};
+use crate::TinySkiaDisplay;
+
+/// How a [`FontText`] bounded by [`FontText::bounds`] breaks its lines when
+/// they would otherwise exceed the bounding box width.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum LineBreak {
+    /// Never break; a line may overflow the bounding box. This is the
+    /// default, matching a plain [`FontText::new`] with no bounds.
+    #[default]
+    Never,
+    /// Break at whitespace word boundaries, falling back to a
+    /// grapheme-cluster break for a single word wider than the box.
+    Word,
+    /// Break at any grapheme-cluster boundary.
+    Character,
+}
+
+/// Where a laid-out line is anchored horizontally within
+/// [`FontText::bounds`].
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum HorizontalAnchor {
+    /// Aligned to the bounding box's left edge. The default.
+    #[default]
+    Start,
+    /// Centered within the bounding box.
+    Center,
+    /// Aligned to the bounding box's right edge.
+    End,
+}
 
 /// A font text object.
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(Copy, Clone, Debug)]
 pub struct FontText<'a> {
     /// The string.
     pub text: &'a str,
 
     /// The position.
     ///
-    /// Defines the top-left starting pixel of the text object.
+    /// Defines the top-left starting pixel of the text object when
+    /// [`Self::bounds`] is `None`; otherwise `Self::bounds`'s top-left
+    /// takes over as the layout origin.
     pub position: Point,
+
+    /// An optional bounding box to wrap and anchor lines within. `None`
+    /// (the default) draws a single line starting at [`Self::position`],
+    /// with no wrapping.
+    pub bounds: Option<Rectangle>,
+
+    /// How lines break within [`Self::bounds`]. Ignored when `bounds` is
+    /// `None`.
+    pub line_break: LineBreak,
+
+    /// How lines are anchored horizontally within [`Self::bounds`]. Ignored
+    /// when `bounds` is `None`.
+    pub horizontal_anchor: HorizontalAnchor,
+
+    /// How the text object is rotated. The rotated text occupies a box
+    /// whose top-left corner is its origin (its unrotated top-left corner).
+    pub rotation: Rotation,
 }
 
 impl<'a> FontText<'a> {
-    /// Creates a text.
+    /// Creates a single unbounded, unwrapped line of text.
     pub const fn new(text: &'a str, position: Point) -> Self {
-        Self { text, position }
+        Self {
+            text,
+            position,
+            bounds: None,
+            line_break: LineBreak::Never,
+            horizontal_anchor: HorizontalAnchor::Start,
+            rotation: Rotation::None,
+        }
+    }
+
+    /// Creates a text object that wraps and anchors its lines within
+    /// `bounds`, per `line_break` and `horizontal_anchor`.
+    pub const fn with_bounds(
+        text: &'a str,
+        bounds: Rectangle,
+        line_break: LineBreak,
+        horizontal_anchor: HorizontalAnchor,
+    ) -> Self {
+        Self {
+            text,
+            position: bounds.top_left,
+            bounds: Some(bounds),
+            line_break,
+            horizontal_anchor,
+            rotation: Rotation::None,
+        }
+    }
+
+    /// Returns a new `FontText` with `rotation` applied (see [`Self::rotation`]).
+    pub fn rotated(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
     }
 
     /// Attaches a text style to the text object.
-    pub fn into_styled<C>(self, style: FontStyle<C>) -> Styled<Self, FontStyle<C>>
+    pub fn into_styled<C>(self, style: FontStyle<C>) -> StyledFontText<'a, C>
     where
         C: PixelColor,
     {
-        Styled::new(self, style)
+        StyledFontText { text: self, style }
     }
-}
 
-impl Transform for FontText<'_> {
-    fn translate(&self, by: Point) -> Self {
+    /// Returns a new `FontText` translated by `by`.
+    pub fn translate(&self, by: Point) -> Self {
         Self {
             position: self.position + by,
+            bounds: self.bounds.map(|bounds| Rectangle::new(bounds.top_left + by, bounds.size)),
             ..*self
         }
     }
 
-    fn translate_mut(&mut self, by: Point) -> &mut Self {
+    /// Translates this `FontText` in place.
+    pub fn translate_mut(&mut self, by: Point) -> &mut Self {
         self.position += by;
+        self.bounds = self
+            .bounds
+            .map(|bounds| Rectangle::new(bounds.top_left + by, bounds.size));
 
         self
     }
+
+    /// This text's origin: [`Self::bounds`]'s top-left corner if set,
+    /// otherwise [`Self::position`].
+    fn origin(&self) -> Point {
+        self.bounds.map_or(self.position, |bounds| bounds.top_left)
+    }
 }
 
-impl<C> Drawable<C> for &Styled<FontText<'_>, FontStyle<C>>
+/// A [`FontText`] paired with a [`FontStyle`], ready to be drawn.
+///
+/// Outline glyph rasterization is memoized by the underlying
+/// [`Font`](crate::font::Font)'s glyph cache (keyed by font identity, glyph
+/// id and pixel size), so redrawing the same styled text every frame only
+/// rasterizes each glyph once while it stays cached. A style with inline
+/// font bytes ([`FontSource::Inline`](crate::font::FontSource::Inline))
+/// loads a fresh `Font`, with an empty cache, on every draw, and color
+/// glyphs are decoded again on every draw.
+pub struct StyledFontText<'a, C>
 where
     C: PixelColor,
 {
-    fn draw(self, display: &mut TinySkiaDisplay<C>) -> Result<(), String> {
-        display.draw_iter(self.into_iter())
-    }
+    text: FontText<'a>,
+    style: FontStyle<C>,
 }
 
-// impl<C> Drawable<C> for &Styled<FontText<'_>, FontStyle<C>>
-// where
-//     C: PixelColor,
-// {
-//     fn draw(self, display: &mut TinySkiaDisplay<C>) -> Result<(), String> {
-//         // display.draw_iter(self.into_iter())
-//     }
-// }
+impl<'a, C> StyledFontText<'a, C>
+where
+    C: PixelColor + From<<C as PixelColor>::Raw> + Into<embedded_graphics_core::pixelcolor::Rgb888>,
+{
+    /// Resolves this text's style against `display`'s font registry and
+    /// draws it, returning the position just past the drawn text.
+    ///
+    /// Returns an error if [`FontStyle::font_source`] names a family that
+    /// isn't registered.
+    pub fn draw(&self, display: &mut TinySkiaDisplay<C>) -> Result<Point, String> {
+        let text_style = display
+            .text_style(&self.style)
+            .ok_or("Font family not registered")?;
+
+        match self.text.rotation {
+            Rotation::None => Ok(self.draw_into_pixmap(&text_style, self.text.origin(), display)),
+            rotation => self.draw_rotated(&text_style, rotation, display),
+        }
+    }
+
+    /// Like [`Self::draw_into`], but draws directly into `display`'s
+    /// `Pixmap` so color glyphs (embedded bitmaps and layered `COLR`/`CPAL`
+    /// outlines) composite correctly, instead of the generic `DrawTarget`
+    /// path that can only threshold coverage into a single flat color.
+    /// Infallible, unlike `draw_into`, since it bypasses `DrawTarget`
+    /// entirely.
+    fn draw_into_pixmap(
+        &self,
+        text_style: &FontTextStyle<C>,
+        origin: Point,
+        display: &mut TinySkiaDisplay<C>,
+    ) -> Point {
+        let gamma_lut = text_style.gamma_lut();
+
+        match self.text.bounds {
+            None => {
+                text_style.draw_string_into_pixmap(self.text.text, origin, &gamma_lut, &mut display.pix_map)
+            }
+            Some(bounds) => {
+                let wrap_mode = match self.text.line_break {
+                    LineBreak::Never => None,
+                    LineBreak::Word => Some(WrapMode::Word),
+                    LineBreak::Character => Some(WrapMode::Character),
+                };
+
+                let measure = |chunk: &str| {
+                    text_style.measure_string(chunk, Point::zero()).bounding_box.size.width as f32
+                };
+
+                let lines = match wrap_mode {
+                    Some(mode) => {
+                        layout_lines_with_mode(self.text.text, Some(bounds.size.width), mode, measure)
+                    }
+                    None => layout_lines(self.text.text, None, measure),
+                };
+
+                let line_height = TextRenderer::line_height(text_style) as i32;
+                let mut p = origin;
+
+                for (i, line) in lines.iter().enumerate() {
+                    let line_width = measure(&line.text) as i32;
+                    let x_offset = match self.text.horizontal_anchor {
+                        HorizontalAnchor::Start => 0,
+                        HorizontalAnchor::Center => (bounds.size.width as i32 - line_width) / 2,
+                        HorizontalAnchor::End => bounds.size.width as i32 - line_width,
+                    };
+
+                    let line_position =
+                        Point::new(origin.x + x_offset, origin.y + i as i32 * line_height);
+
+                    // Same already-visual-order concern as `draw_into`:
+                    // draw the pre-laid-out line directly, without
+                    // re-running BiDi layout.
+                    p = text_style.draw_line_into_pixmap(
+                        &line.text,
+                        line_position,
+                        &gamma_lut,
+                        &mut display.pix_map,
+                    );
+                }
+
+                p
+            }
+        }
+    }
+
+    /// Lays out and draws this text starting at `origin`, into any
+    /// `DrawTarget`. Used for the rotated path (drawing into a scratch
+    /// buffer first); the unrotated path uses [`Self::draw_into_pixmap`].
+    fn draw_into<D>(
+        &self,
+        text_style: &FontTextStyle<C>,
+        origin: Point,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        match self.text.bounds {
+            None => text_style.draw_string(self.text.text, origin, target),
+            Some(bounds) => {
+                let wrap_mode = match self.text.line_break {
+                    LineBreak::Never => None,
+                    LineBreak::Word => Some(WrapMode::Word),
+                    LineBreak::Character => Some(WrapMode::Character),
+                };
+
+                let measure = |chunk: &str| {
+                    text_style.measure_string(chunk, Point::zero()).bounding_box.size.width as f32
+                };
+
+                let lines = match wrap_mode {
+                    Some(mode) => {
+                        layout_lines_with_mode(self.text.text, Some(bounds.size.width), mode, measure)
+                    }
+                    None => layout_lines(self.text.text, None, measure),
+                };
+
+                let line_height = TextRenderer::line_height(text_style) as i32;
+                let gamma_lut = text_style.gamma_lut();
+                let mut p = origin;
+
+                for (i, line) in lines.iter().enumerate() {
+                    let line_width = measure(&line.text) as i32;
+                    let x_offset = match self.text.horizontal_anchor {
+                        HorizontalAnchor::Start => 0,
+                        HorizontalAnchor::Center => (bounds.size.width as i32 - line_width) / 2,
+                        HorizontalAnchor::End => bounds.size.width as i32 - line_width,
+                    };
+
+                    let line_position =
+                        Point::new(origin.x + x_offset, origin.y + i as i32 * line_height);
+
+                    // `line.text` is already wrapped and BiDi-reordered into
+                    // visual order by `layout_lines[_with_mode]` above;
+                    // `draw_line` draws it as-is, unlike `draw_string`, which
+                    // would re-run that layout (and so re-reorder an already
+                    // visual-order RTL line).
+                    p = text_style.draw_line(&line.text, line_position, &gamma_lut, target)?;
+                }
+
+                Ok(p)
+            }
+        }
+    }
+
+    /// The unrotated size this text would occupy if drawn at the origin:
+    /// `bounds`'s size if set, otherwise the text's measured extent.
+    fn unrotated_size(&self, text_style: &FontTextStyle<C>) -> Size {
+        match self.text.bounds {
+            Some(bounds) => bounds.size,
+            None => {
+                text_style
+                    .measure_string(self.text.text, Point::zero())
+                    .bounding_box
+                    .size
+            }
+        }
+    }
+
+    /// Draws this text rotated, with the rotated box's top-left corner at
+    /// its origin: lays it out normally into
+    /// a scratch buffer, then remaps every drawn pixel through `rotation`
+    /// before drawing it into `display`. Axis-aligned 90° multiples are
+    /// exact integer pixel transforms, with no resampling.
+    fn draw_rotated(
+        &self,
+        text_style: &FontTextStyle<C>,
+        rotation: Rotation,
+        display: &mut TinySkiaDisplay<C>,
+    ) -> Result<Point, String> {
+        let origin = self.text.origin();
+        let unrotated_size = self.unrotated_size(text_style);
+
+        let mut buffer = PixelBuffer::new(unrotated_size);
+        // Drawing into a `PixelBuffer` never fails (`Error = Infallible`).
+        let _ = self.draw_into(text_style, Point::zero(), &mut buffer);
+
+        let pixels = buffer
+            .into_pixels()
+            .into_iter()
+            .map(|pixel| Pixel(origin + rotation.transform_point(pixel.0, unrotated_size), pixel.1));
+
+        display.draw_iter(pixels)?;
+
+        let rotated_size = rotation.transform_size(unrotated_size);
+        Ok(origin + Point::new(rotated_size.width as i32, 0))
+    }
+}
@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+
+use rusttype::GlyphId;
+use tiny_skia::{Mask, Rect};
+
+/// Identifies a single cached glyph: the font it belongs to, its glyph id
+/// and the pixel size it was rasterized at.
+///
+/// `font_size` is quantized to an integer before being used as a cache key so
+/// that sub-pixel size jitter (e.g. `12.001` vs. `12.0`) does not defeat the
+/// cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GlyphKey {
+    pub font_id: u64,
+    pub glyph_id: GlyphId,
+    pub font_size: u32,
+}
+
+impl GlyphKey {
+    pub fn new(font_id: u64, glyph_id: GlyphId, font_size: f32) -> Self {
+        GlyphKey {
+            font_id,
+            glyph_id,
+            font_size: font_size.round() as u32,
+        }
+    }
+}
+
+/// A rasterized glyph living inside the atlas.
+#[derive(Debug, Clone, Copy)]
+pub struct AtlasEntry {
+    /// The glyph's coverage rectangle inside the atlas.
+    pub atlas_rect: Rect,
+
+    /// Horizontal bearing, in pixels, from the pen position to the left edge
+    /// of the coverage bitmap.
+    pub left: i32,
+
+    /// Vertical bearing, in pixels, from the baseline to the top edge of the
+    /// coverage bitmap.
+    pub top: i32,
+
+    /// Horizontal advance of the glyph, in pixels.
+    pub advance: f32,
+}
+
+struct Slot {
+    entry: AtlasEntry,
+    last_used: u64,
+    /// Whether `entry.atlas_rect` was actually packed by [`GlyphCache::allocate`]
+    /// (and so should be freed back into [`GlyphCache::free_rects`] on
+    /// eviction), as opposed to the shared 1x1 placeholder rect used for
+    /// zero-size glyphs.
+    allocated: bool,
+}
+
+/// An LRU-managed atlas of rasterized glyph coverage bitmaps.
+///
+/// Glyphs are packed into a single growable alpha mask using a simple
+/// shelf/row allocator: entries are placed left-to-right until a row is
+/// full, then a new row is started below it. Once `capacity` glyphs are
+/// cached, inserting a new glyph evicts the least-recently-used one to make
+/// room; its atlas rect is freed back into a free list so a
+/// same-sized future glyph can reuse it instead of growing the atlas
+/// further, keeping a long-running cache's footprint bounded.
+pub struct GlyphCache {
+    atlas: Mask,
+    slots: HashMap<GlyphKey, Slot>,
+    /// Atlas rects reclaimed from evicted glyphs, available for reuse by
+    /// [`Self::allocate`] before it grows the atlas or advances the shelf
+    /// cursor.
+    free_rects: Vec<Rect>,
+    capacity: usize,
+    clock: u64,
+    cursor_x: u32,
+    cursor_y: u32,
+    row_height: u32,
+    padding: u32,
+}
+
+impl GlyphCache {
+    /// Creates a glyph cache holding at most `capacity` glyphs, backed by a
+    /// 256x256 atlas.
+    pub fn new(capacity: usize) -> Self {
+        Self::with_atlas_size(capacity, 256, 256)
+    }
+
+    /// Creates a glyph cache with an explicit atlas size.
+    pub fn with_atlas_size(capacity: usize, width: u32, height: u32) -> Self {
+        GlyphCache {
+            atlas: Mask::new(width, height).expect("glyph atlas must have a non-zero size"),
+            slots: HashMap::new(),
+            free_rects: Vec::new(),
+            capacity,
+            clock: 0,
+            cursor_x: 0,
+            cursor_y: 0,
+            row_height: 0,
+            padding: 1,
+        }
+    }
+
+    /// The atlas backing every cached glyph's coverage bitmap.
+    pub fn atlas(&self) -> &Mask {
+        &self.atlas
+    }
+
+    /// Looks up a cached glyph, marking it as the most-recently used.
+    pub fn get(&mut self, key: &GlyphKey) -> Option<AtlasEntry> {
+        self.clock += 1;
+        let clock = self.clock;
+        self.slots.get_mut(key).map(|slot| {
+            slot.last_used = clock;
+            slot.entry
+        })
+    }
+
+    /// Packs a rasterized glyph coverage bitmap into the atlas and records
+    /// it under `key`, evicting the least-recently-used glyph first if the
+    /// cache is at capacity.
+    pub fn insert(
+        &mut self,
+        key: GlyphKey,
+        coverage: &[u8],
+        width: u32,
+        height: u32,
+        left: i32,
+        top: i32,
+        advance: f32,
+    ) -> AtlasEntry {
+        if self.slots.len() >= self.capacity {
+            self.evict_lru();
+        }
+
+        if width == 0 || height == 0 {
+            let entry = AtlasEntry {
+                atlas_rect: Rect::from_xywh(0.0, 0.0, 1.0, 1.0).unwrap(),
+                left,
+                top,
+                advance,
+            };
+            self.remember(key, entry, false);
+            return entry;
+        }
+
+        let atlas_rect = self.allocate(width, height);
+        self.blit_coverage(&atlas_rect, coverage, width, height);
+
+        let entry = AtlasEntry {
+            atlas_rect,
+            left,
+            top,
+            advance,
+        };
+        self.remember(key, entry, true);
+        entry
+    }
+
+    fn remember(&mut self, key: GlyphKey, entry: AtlasEntry, allocated: bool) {
+        self.clock += 1;
+        self.slots.insert(
+            key,
+            Slot {
+                entry,
+                last_used: self.clock,
+                allocated,
+            },
+        );
+    }
+
+    fn allocate(&mut self, width: u32, height: u32) -> Rect {
+        if let Some(i) = self
+            .free_rects
+            .iter()
+            .position(|r| r.width() as u32 == width && r.height() as u32 == height)
+        {
+            return self.free_rects.remove(i);
+        }
+
+        if width + self.padding > self.atlas.width() {
+            self.grow_atlas(width + self.padding, self.atlas.height());
+        }
+
+        let atlas_width = self.atlas.width();
+        if self.cursor_x + width + self.padding > atlas_width {
+            self.cursor_x = 0;
+            self.cursor_y += self.row_height + self.padding;
+            self.row_height = 0;
+        }
+
+        if self.cursor_y + height + self.padding > self.atlas.height() {
+            self.grow_atlas(atlas_width, self.cursor_y + height + self.padding);
+        }
+
+        let rect = Rect::from_xywh(
+            self.cursor_x as f32,
+            self.cursor_y as f32,
+            width as f32,
+            height as f32,
+        )
+        .expect("glyph atlas rect must be valid");
+
+        self.cursor_x += width + self.padding;
+        self.row_height = self.row_height.max(height);
+
+        rect
+    }
+
+    /// Grows the atlas to at least `min_width` x `min_height`, preserving
+    /// every already-packed glyph's pixel data at its existing coordinates.
+    /// The shelf allocator in [`Self::allocate`] never shrinks its cursor, so
+    /// growing (rather than repacking) is the only way to make room once the
+    /// current atlas is full — without this, `allocate` would keep handing
+    /// out rects past the atlas bounds and `blit_coverage` would panic.
+    fn grow_atlas(&mut self, min_width: u32, min_height: u32) {
+        let new_width = self.atlas.width().max(min_width);
+        let mut new_height = self.atlas.height().max(1);
+        while new_height < min_height {
+            new_height *= 2;
+        }
+
+        if new_width == self.atlas.width() && new_height == self.atlas.height() {
+            return;
+        }
+
+        let mut new_atlas =
+            Mask::new(new_width, new_height).expect("glyph atlas must have a non-zero size");
+
+        let old_width = self.atlas.width() as usize;
+        let old_data = self.atlas.data();
+        let new_data = new_atlas.data_mut();
+        for row in 0..self.atlas.height() as usize {
+            let src = &old_data[row * old_width..(row + 1) * old_width];
+            let dst_start = row * new_width as usize;
+            new_data[dst_start..dst_start + old_width].copy_from_slice(src);
+        }
+
+        self.atlas = new_atlas;
+    }
+
+    fn blit_coverage(&mut self, rect: &Rect, coverage: &[u8], width: u32, height: u32) {
+        let atlas_width = self.atlas.width() as usize;
+        let data = self.atlas.data_mut();
+        let origin_x = rect.left() as usize;
+        let origin_y = rect.top() as usize;
+
+        for row in 0..height as usize {
+            let src = &coverage[row * width as usize..(row + 1) * width as usize];
+            let dst_start = (origin_y + row) * atlas_width + origin_x;
+            data[dst_start..dst_start + width as usize].copy_from_slice(src);
+        }
+    }
+
+    fn evict_lru(&mut self) {
+        let lru_key = self
+            .slots
+            .iter()
+            .min_by_key(|(_, slot)| slot.last_used)
+            .map(|(&key, _)| key);
+
+        if let Some(key) = lru_key {
+            if let Some(slot) = self.slots.remove(&key) {
+                if slot.allocated {
+                    self.free_rects.push(slot.entry.atlas_rect);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(font_id: u64, glyph_id: u32) -> GlyphKey {
+        GlyphKey::new(font_id, GlyphId(glyph_id), 12.0)
+    }
+
+    #[test]
+    fn insert_and_get_round_trips_an_entry() {
+        let mut cache = GlyphCache::new(4);
+        let entry = cache.insert(key(0, 1), &[255; 4], 2, 2, 0, 0, 2.0);
+
+        assert_eq!(cache.get(&key(0, 1)), Some(entry));
+    }
+
+    #[test]
+    fn zero_size_glyph_uses_shared_placeholder_rect() {
+        let mut cache = GlyphCache::new(4);
+        let entry = cache.insert(key(0, 1), &[], 0, 0, 0, 0, 1.0);
+
+        assert_eq!(entry.atlas_rect.width(), 1.0);
+        assert_eq!(entry.atlas_rect.height(), 1.0);
+    }
+
+    #[test]
+    fn eviction_drops_the_least_recently_used_entry() {
+        let mut cache = GlyphCache::new(2);
+        cache.insert(key(0, 1), &[255; 4], 2, 2, 0, 0, 2.0);
+        cache.insert(key(0, 2), &[255; 4], 2, 2, 0, 0, 2.0);
+        // Touch glyph 1 so glyph 2 becomes the least-recently used.
+        cache.get(&key(0, 1));
+        cache.insert(key(0, 3), &[255; 4], 2, 2, 0, 0, 2.0);
+
+        assert!(cache.get(&key(0, 1)).is_some());
+        assert!(cache.get(&key(0, 2)).is_none());
+        assert!(cache.get(&key(0, 3)).is_some());
+    }
+
+    #[test]
+    fn evicted_atlas_rect_is_reused_instead_of_growing_the_atlas() {
+        let mut cache = GlyphCache::with_atlas_size(1, 256, 256);
+        let first = cache.insert(key(0, 1), &[255; 16], 4, 4, 0, 0, 4.0);
+        // Forces eviction of glyph 1 (capacity is 1), freeing its rect,
+        // which this same-sized insert should reuse rather than advancing
+        // the shelf cursor further.
+        let second = cache.insert(key(0, 2), &[255; 16], 4, 4, 0, 0, 4.0);
+
+        assert_eq!(second.atlas_rect.x(), first.atlas_rect.x());
+        assert_eq!(second.atlas_rect.y(), first.atlas_rect.y());
+        assert!(cache.free_rects.is_empty());
+    }
+
+    #[test]
+    fn atlas_grows_when_a_glyph_does_not_fit() {
+        let mut cache = GlyphCache::with_atlas_size(8, 4, 4);
+        let initial_width = cache.atlas().width();
+
+        cache.insert(key(0, 1), &[255; 64], 8, 8, 0, 0, 8.0);
+
+        assert!(cache.atlas().width() >= initial_width);
+        assert!(cache.atlas().height() >= 8);
+    }
+}
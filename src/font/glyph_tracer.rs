@@ -0,0 +1,51 @@
+use rusttype::OutlineBuilder;
+use tiny_skia::PathBuilder;
+
+/// Traces a `rusttype` glyph outline into a tiny-skia [`PathBuilder`].
+///
+/// `position` is added to every emitted point, so the caller can move the
+/// outline to where it should be filled (e.g. the top-left of a glyph's own
+/// coverage mask).
+pub struct GlyphTracer {
+    /// The path builder that accumulates the glyph outline.
+    pub path_builder: PathBuilder,
+
+    /// The pixel position the glyph outline is offset by.
+    pub position: rusttype::Point<f32>,
+}
+
+impl OutlineBuilder for GlyphTracer {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.path_builder
+            .move_to(self.position.x + x, self.position.y + y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.path_builder
+            .line_to(self.position.x + x, self.position.y + y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.path_builder.quad_to(
+            self.position.x + x1,
+            self.position.y + y1,
+            self.position.x + x,
+            self.position.y + y,
+        );
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.path_builder.cubic_to(
+            self.position.x + x1,
+            self.position.y + y1,
+            self.position.x + x2,
+            self.position.y + y2,
+            self.position.x + x,
+            self.position.y + y,
+        );
+    }
+
+    fn close(&mut self) {
+        self.path_builder.close();
+    }
+}
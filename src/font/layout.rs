@@ -0,0 +1,247 @@
+use unicode_bidi::BidiInfo;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A single laid-out visual line of text, already reordered according to
+/// the Unicode Bidirectional Algorithm.
+#[derive(Debug, Clone, Default)]
+pub struct LaidOutLine {
+    /// The line's text, in visual (left-to-right-on-screen) order.
+    pub text: String,
+}
+
+/// Which boundaries a wrapped line may break at, when a `max_width` is set.
+/// See [`layout_lines_with_mode`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum WrapMode {
+    /// Break at whitespace word boundaries, falling back to a
+    /// grapheme-cluster break for a single word wider than `max_width`.
+    Word,
+    /// Break at any grapheme-cluster boundary, ignoring word boundaries.
+    Character,
+}
+
+/// Splits `text` into wrapped, BiDi-reordered visual lines, breaking at
+/// word boundaries (see [`layout_lines_with_mode`] for character-boundary
+/// wrapping).
+///
+/// Explicit `\n` always starts a new line, and consecutive newlines still
+/// advance to an (empty) line. When `max_width` is set, lines are
+/// additionally broken at word boundaries so no line's measured advance
+/// (via `measure`) exceeds it; a single word wider than `max_width` falls
+/// back to a grapheme-cluster break rather than overflowing silently.
+pub fn layout_lines(
+    text: &str,
+    max_width: Option<u32>,
+    measure: impl Fn(&str) -> f32,
+) -> Vec<LaidOutLine> {
+    layout_lines_with_mode(text, max_width, WrapMode::Word, measure)
+}
+
+/// Like [`layout_lines`], but with an explicit [`WrapMode`] controlling
+/// where a wrapped line may break.
+pub fn layout_lines_with_mode(
+    text: &str,
+    max_width: Option<u32>,
+    mode: WrapMode,
+    measure: impl Fn(&str) -> f32,
+) -> Vec<LaidOutLine> {
+    let mut lines = Vec::new();
+
+    for paragraph in text.split('\n') {
+        let reordered = reorder_bidi(paragraph);
+
+        match max_width {
+            Some(max_width) => match mode {
+                WrapMode::Word => {
+                    wrap_paragraph(&reordered, max_width as f32, &measure, &mut lines)
+                }
+                WrapMode::Character if reordered.is_empty() => lines.push(LaidOutLine::default()),
+                WrapMode::Character => {
+                    wrap_graphemes(&reordered, max_width as f32, &measure, &mut lines)
+                }
+            },
+            None => lines.push(LaidOutLine { text: reordered }),
+        }
+    }
+
+    lines
+}
+
+/// Resolves and reorders a single paragraph's bidirectional runs into
+/// visual order.
+fn reorder_bidi(paragraph: &str) -> String {
+    if paragraph.is_empty() {
+        return String::new();
+    }
+
+    let bidi_info = BidiInfo::new(paragraph, None);
+    bidi_info
+        .paragraphs
+        .iter()
+        .map(|para| bidi_info.reorder_line(para, para.range.clone()).into_owned())
+        .collect()
+}
+
+/// Greedily accumulates word tokens onto a line until the next token would
+/// exceed `max_width`, per `measure`.
+fn wrap_paragraph(
+    paragraph: &str,
+    max_width: f32,
+    measure: &impl Fn(&str) -> f32,
+    lines: &mut Vec<LaidOutLine>,
+) {
+    if paragraph.is_empty() {
+        lines.push(LaidOutLine::default());
+        return;
+    }
+
+    let mut current = String::new();
+
+    for word in split_words(paragraph) {
+        let is_whitespace = word.chars().all(char::is_whitespace);
+
+        if !current.is_empty() {
+            let candidate = format!("{}{}", current, word);
+
+            if measure(&candidate) > max_width {
+                let line = current.trim_end().to_string();
+                current.clear();
+                if !line.is_empty() {
+                    lines.push(LaidOutLine { text: line });
+                }
+
+                // The whitespace run a line breaks at is dropped rather
+                // than carried over to the start of the next line.
+                if is_whitespace {
+                    continue;
+                }
+            }
+        }
+
+        if current.is_empty() && measure(&word) > max_width {
+            wrap_graphemes(&word, max_width, measure, lines);
+            continue;
+        }
+
+        current.push_str(&word);
+    }
+
+    if !current.is_empty() {
+        lines.push(LaidOutLine { text: current });
+    }
+}
+
+/// Breaks `word` (a single word wider than `max_width` on its own, or a
+/// whole paragraph in [`WrapMode::Character`]) one grapheme cluster at a
+/// time.
+fn wrap_graphemes(
+    word: &str,
+    max_width: f32,
+    measure: &impl Fn(&str) -> f32,
+    lines: &mut Vec<LaidOutLine>,
+) {
+    let mut current = String::new();
+
+    for grapheme in word.graphemes(true) {
+        let candidate = format!("{}{}", current, grapheme);
+
+        if !current.is_empty() && measure(&candidate) > max_width {
+            lines.push(LaidOutLine {
+                text: std::mem::take(&mut current),
+            });
+        }
+
+        current.push_str(grapheme);
+    }
+
+    if !current.is_empty() {
+        lines.push(LaidOutLine { text: current });
+    }
+}
+
+/// Splits `text` into alternating runs of non-whitespace and whitespace
+/// grapheme clusters, so a greedy wrapper can break between them while
+/// still being able to re-join tokens back into the original text.
+fn split_words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut current_is_whitespace = false;
+
+    for grapheme in text.graphemes(true) {
+        let is_whitespace = grapheme.chars().all(char::is_whitespace);
+
+        if !current.is_empty() && is_whitespace != current_is_whitespace {
+            words.push(std::mem::take(&mut current));
+        }
+
+        current.push_str(grapheme);
+        current_is_whitespace = is_whitespace;
+    }
+
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A monospace stand-in measure: one unit per grapheme cluster.
+    fn measure_graphemes(text: &str) -> f32 {
+        text.graphemes(true).count() as f32
+    }
+
+    fn lines(text: &str, max_width: Option<u32>) -> Vec<String> {
+        layout_lines(text, max_width, measure_graphemes)
+            .into_iter()
+            .map(|line| line.text)
+            .collect()
+    }
+
+    #[test]
+    fn unwrapped_text_keeps_explicit_newlines() {
+        assert_eq!(lines("foo\nbar", None), vec!["foo", "bar"]);
+    }
+
+    #[test]
+    fn consecutive_newlines_produce_an_empty_line() {
+        assert_eq!(lines("foo\n\nbar", None), vec!["foo", "", "bar"]);
+    }
+
+    #[test]
+    fn empty_paragraph_wraps_to_a_single_empty_line() {
+        assert_eq!(lines("", Some(10)), vec![""]);
+    }
+
+    #[test]
+    fn word_wrap_breaks_at_whitespace() {
+        assert_eq!(lines("aa bb cc", Some(5)), vec!["aa bb", "cc"]);
+    }
+
+    #[test]
+    fn oversized_first_word_falls_back_to_grapheme_wrapping() {
+        // Regression test: an oversized word used to only be
+        // grapheme-wrapped when it wasn't the first token on the line,
+        // silently overflowing otherwise.
+        assert_eq!(lines("abcdef", Some(3)), vec!["abc", "def"]);
+    }
+
+    #[test]
+    fn oversized_word_after_other_words_is_grapheme_wrapped() {
+        assert_eq!(lines("a abcdef", Some(3)), vec!["a", "abc", "def"]);
+    }
+
+    #[test]
+    fn character_wrap_breaks_at_any_grapheme() {
+        assert_eq!(
+            layout_lines_with_mode("abcdef", Some(3), WrapMode::Character, measure_graphemes)
+                .into_iter()
+                .map(|line| line.text)
+                .collect::<Vec<_>>(),
+            vec!["abc", "def"]
+        );
+    }
+}
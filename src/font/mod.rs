@@ -1,4 +1,8 @@
+use std::cell::{Ref, RefCell};
 use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 use embedded_graphics_core::{
     draw_target::DrawTarget,
@@ -6,11 +10,41 @@ use embedded_graphics_core::{
     primitives::Rectangle,
     text::{CharacterStyle, DecorationColor, TextMetrics, TextRenderer, VerticalAlignment},
 };
-use tiny_skia::{FillRule, Paint, PathBuilder, Pixmap, Transform};
-
+use tiny_skia::{Color, FillRule, Mask, Paint, PathBuilder, Pixmap, Rect, Shader, Transform};
+
+mod bitmap_font;
+mod color_glyph;
+mod font_descriptor;
+mod font_style;
+mod font_text;
+mod glyph_cache;
 mod glyph_tracer;
-
+mod layout;
+mod rotation;
+
+pub use self::bitmap_font::*;
+pub use self::color_glyph::*;
+pub use self::font_descriptor::*;
+pub use self::font_style::*;
+pub use self::font_text::*;
+pub use self::glyph_cache::*;
 pub use self::glyph_tracer::*;
+pub use self::layout::*;
+pub use self::rotation::Rotation;
+
+/// Default number of glyphs a [`Font`]'s cache keeps rasterized before
+/// evicting the least-recently-used entry.
+const DEFAULT_GLYPH_CACHE_CAPACITY: usize = 256;
+
+/// Assigns each loaded [`Font`] a unique id used in glyph cache keys.
+static NEXT_FONT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Default gamma applied to glyph coverage, matching the value used by
+/// WebRender's glyph rasterizer.
+const DEFAULT_GAMMA: f32 = 1.8;
+
+/// Default contrast enhancement factor (no boost).
+const DEFAULT_CONTRAST: f32 = 1.0;
 
 pub struct FontTextStyle<C: PixelColor> {
     /// Text color.
@@ -28,6 +62,20 @@ pub struct FontTextStyle<C: PixelColor> {
     /// Font size.
     pub font_size: u32,
 
+    /// Maximum line width, in pixels, before text wraps onto a new line.
+    /// `None` disables wrapping (the default); explicit `\n` always starts
+    /// a new line regardless of this setting.
+    pub max_width: Option<u32>,
+
+    /// Gamma used to correct glyph coverage before it is composited, so
+    /// anti-aliased edges don't look thin and muddy. See
+    /// [`FontTextStyleBuilder::gamma`].
+    pub gamma: f32,
+
+    /// Contrast enhancement factor applied to glyph coverage before gamma
+    /// correction. See [`FontTextStyleBuilder::contrast`].
+    pub contrast: f32,
+
     /// Font.
     font: Font<C>,
 }
@@ -104,6 +152,211 @@ impl<C: PixelColor> FontTextStyle<C> {
 
         Ok(())
     }
+
+    /// The `rusttype` scale corresponding to this style's `font_size`.
+    fn scale(&self) -> rusttype::Scale {
+        rusttype::Scale::uniform(self.font_size as f32)
+    }
+
+    /// Builds the gamma-correction lookup table used to remap glyph
+    /// coverage before it is composited, per [`Self::gamma`] and
+    /// [`Self::contrast`]: `gamma_lut[a] = 255 * (a/255 * contrast)^(1/gamma)`.
+    fn gamma_lut(&self) -> [u8; 256] {
+        build_gamma_lut(self.gamma, self.contrast)
+    }
+
+    /// The width, in pixels, that a chunk of text occupies when laid out
+    /// with this style's font and size.
+    fn measure_advance(&self, text: &str) -> f32 {
+        self.font.measure_text(text, self.font_size as f64).0 as f32
+    }
+
+    /// Lays out `text` into wrapped, BiDi-reordered visual lines per
+    /// `self.max_width`.
+    fn layout(&self, text: &str) -> Vec<LaidOutLine> {
+        layout_lines(text, self.max_width, |chunk| self.measure_advance(chunk))
+    }
+
+    /// Draws a single already-laid-out visual line: its background, glyph
+    /// coverage and decorations.
+    fn draw_line<D>(
+        &self,
+        text: &str,
+        position: Point,
+        gamma_lut: &[u8; 256],
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let scale = self.scale();
+        let v_metrics = self.font.inner.v_metrics(scale);
+        let offset = rusttype::point(0.0, v_metrics.ascent);
+
+        let glyphs: Vec<rusttype::PositionedGlyph> =
+            self.font.inner.layout(text, scale, offset).collect();
+
+        let width = glyphs
+            .iter()
+            .rev()
+            .map(|g| g.position().x as f32 + g.unpositioned().h_metrics().advance_width)
+            .next()
+            .unwrap_or(0.0)
+            .ceil() as u32;
+
+        self.draw_background(width, position, target)?;
+
+        for g in glyphs.iter() {
+            let bbox = match g.pixel_bounding_box() {
+                Some(bbox) => bbox,
+                None => continue,
+            };
+
+            if let (Some(entry), Some(text_color)) =
+                (self.font.cached_glyph(g, self.font_size as f32), self.text_color)
+            {
+                let glyph_origin = Point::new(position.x + bbox.min.x, position.y + bbox.min.y);
+                draw_glyph_coverage(
+                    &self.font.cache(),
+                    &entry,
+                    glyph_origin,
+                    text_color,
+                    gamma_lut,
+                    target,
+                )?;
+            }
+        }
+
+        self.draw_strikethrough(width, position, target)?;
+        self.draw_underline(width, position, target)?;
+
+        Ok(position + Point::new(width as i32, 0))
+    }
+
+    /// Like [`Self::draw_line`], but draws directly into a `tiny_skia::Pixmap`
+    /// rather than a generic `DrawTarget`, so color glyphs (embedded
+    /// `sbix`/`CBDT` bitmaps or layered `COLR`/`CPAL` outlines) can
+    /// composite their own per-pixel color — something a generic
+    /// `DrawTarget` has no way to express for an arbitrary `PixelColor`.
+    /// Used for the common unrotated draw path; the rotated path still
+    /// goes through [`Self::draw_line`] against a scratch `PixelBuffer`.
+    /// A glyph is drawn only if it has an outline pixel bounding box, so a
+    /// bitmap-only color glyph with no outline is skipped.
+    pub(crate) fn draw_line_into_pixmap(
+        &self,
+        text: &str,
+        position: Point,
+        gamma_lut: &[u8; 256],
+        pix_map: &mut Pixmap,
+    ) -> Point
+    where
+        C: Into<embedded_graphics_core::pixelcolor::Rgb888>,
+    {
+        let scale = self.scale();
+        let v_metrics = self.font.inner.v_metrics(scale);
+        let offset = rusttype::point(0.0, v_metrics.ascent);
+
+        let glyphs: Vec<rusttype::PositionedGlyph> =
+            self.font.inner.layout(text, scale, offset).collect();
+
+        let width = glyphs
+            .iter()
+            .rev()
+            .map(|g| g.position().x as f32 + g.unpositioned().h_metrics().advance_width)
+            .next()
+            .unwrap_or(0.0)
+            .ceil() as u32;
+
+        if width > 0 {
+            if let Some(background_color) = self.background_color {
+                self.fill_rect_into_pixmap(position, width, self.font_size, background_color, pix_map);
+            }
+        }
+
+        let face = self.font.face();
+
+        for g in glyphs.iter() {
+            let bbox = match g.pixel_bounding_box() {
+                Some(bbox) => bbox,
+                None => continue,
+            };
+
+            if let Some(text_color) = self.text_color {
+                let dest_x = position.x + bbox.min.x;
+                let dest_y = position.y + bbox.min.y;
+                let glyph_id = ttf_parser::GlyphId(g.id().0 as u16);
+                let skia_color = crate::to_skia_color(text_color);
+
+                let color_image = face
+                    .as_ref()
+                    .and_then(|face| color_glyph_image(face, glyph_id, skia_color));
+
+                if let Some(image) = color_image {
+                    blit_color_glyph(pix_map, &image, self.font_size as f32, dest_x, dest_y);
+                } else if let Some(entry) = self.font.cached_glyph(g, self.font_size as f32) {
+                    blit_coverage_into_pixmap(
+                        &self.font.cache(),
+                        &entry,
+                        pix_map,
+                        dest_x,
+                        dest_y,
+                        skia_color,
+                        gamma_lut,
+                    );
+                }
+            }
+        }
+
+        if let Some(strikethrough_color) = self.resolve_decoration_color(self.strikethrough_color) {
+            self.fill_rect_into_pixmap(position, width, self.font_size, strikethrough_color, pix_map);
+        }
+        if let Some(underline_color) = self.resolve_decoration_color(self.underline_color) {
+            self.fill_rect_into_pixmap(position, width, self.font_size, underline_color, pix_map);
+        }
+
+        position + Point::new(width as i32, 0)
+    }
+
+    /// Like [`Self::draw_string`](TextRenderer::draw_string), but draws
+    /// directly into a `tiny_skia::Pixmap` via [`Self::draw_line_into_pixmap`].
+    pub(crate) fn draw_string_into_pixmap(
+        &self,
+        text: &str,
+        position: Point,
+        gamma_lut: &[u8; 256],
+        pix_map: &mut Pixmap,
+    ) -> Point
+    where
+        C: Into<embedded_graphics_core::pixelcolor::Rgb888>,
+    {
+        let lines = self.layout(text);
+        let line_height = TextRenderer::line_height(self) as i32;
+        let mut p = position;
+
+        for (i, line) in lines.iter().enumerate() {
+            let line_position = Point::new(position.x, position.y + i as i32 * line_height);
+            p = self.draw_line_into_pixmap(&line.text, line_position, gamma_lut, pix_map);
+        }
+
+        p
+    }
+
+    fn fill_rect_into_pixmap(
+        &self,
+        position: Point,
+        width: u32,
+        height: u32,
+        color: C,
+        pix_map: &mut Pixmap,
+    ) where
+        C: Into<embedded_graphics_core::pixelcolor::Rgb888>,
+    {
+        if let Some(rect) =
+            Rect::from_xywh(position.x as f32, position.y as f32, width as f32, height as f32)
+        {
+            pix_map.fill_rect(rect, &crate::convert_color_to_paint(color), Transform::identity(), None);
+        }
+    }
 }
 
 impl<C: PixelColor> CharacterStyle for FontTextStyle<C> {
@@ -138,6 +391,9 @@ impl<C: PixelColor> FontTextStyleBuilder<C> {
                 font,
                 background_color: None,
                 font_size: 12,
+                max_width: None,
+                gamma: DEFAULT_GAMMA,
+                contrast: DEFAULT_CONTRAST,
                 text_color: None,
                 underline_color: DecorationColor::None,
                 strikethrough_color: DecorationColor::None,
@@ -150,6 +406,31 @@ impl<C: PixelColor> FontTextStyleBuilder<C> {
         self
     }
 
+    /// Wraps text at word (falling back to grapheme) boundaries so no line
+    /// exceeds `max_width` pixels.
+    pub fn max_width(mut self, max_width: u32) -> Self {
+        self.style.max_width = Some(max_width);
+        self
+    }
+
+    /// Sets the gamma used to correct glyph coverage before compositing.
+    ///
+    /// Anti-aliased coverage is blended in linear space by default, which
+    /// makes small light-on-dark text look thin and muddy. A gamma around
+    /// `1.8` (the default) boosts partially-covered edge pixels so glyphs
+    /// read as crisper without changing fully-covered pixels.
+    pub fn gamma(mut self, gamma: f32) -> Self {
+        self.style.gamma = gamma;
+        self
+    }
+
+    /// Sets a contrast enhancement factor applied to glyph coverage before
+    /// gamma correction, for further tuning legibility on a given panel.
+    pub fn contrast(mut self, contrast: f32) -> Self {
+        self.style.contrast = contrast;
+        self
+    }
+
     /// Enables underline using the text color.
     pub fn underline(mut self) -> Self {
         self.style.underline_color = DecorationColor::TextColor;
@@ -203,24 +484,108 @@ impl<C: PixelColor> FontTextStyleBuilder<C> {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Font<C: PixelColor> {
     inner: rusttype::Font<'static>,
+    /// The font's raw bytes, kept around so OpenType tables `rusttype`
+    /// doesn't expose (e.g. `sbix`/`CBDT` color bitmaps) can still be read
+    /// via `ttf-parser`.
+    bytes: Arc<[u8]>,
     pixel_size: u32,
+    /// Identifies this font (and every clone of it) for glyph cache keys.
+    id: u64,
+    /// Rasterized glyph cache, shared by every clone of this `Font`.
+    cache: Rc<RefCell<GlyphCache>>,
     _c: PhantomData<C>,
 }
 
+impl<C: PixelColor> std::fmt::Debug for Font<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Font")
+            .field("pixel_size", &self.pixel_size)
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
 impl<C: PixelColor> Font<C> {
-    pub fn from_bytes(bytes: &'static [u8], pixel_size: u32) -> Result<Self, &'static str> {
-        rusttype::Font::try_from_bytes(bytes)
+    /// Loads a font from its raw bytes, either `&'static` (e.g.
+    /// `include_bytes!`) or owned (e.g. read from disk at runtime, so it
+    /// can live in a [`TinySkiaDisplay`](crate::TinySkiaDisplay) font
+    /// registry).
+    pub fn from_bytes(bytes: impl Into<Vec<u8>>, pixel_size: u32) -> Result<Self, &'static str> {
+        Self::from_bytes_with_cache_capacity(bytes, pixel_size, DEFAULT_GLYPH_CACHE_CAPACITY)
+    }
+
+    /// Like [`Self::from_bytes`], but with an explicit glyph rasterization
+    /// cache capacity instead of [`DEFAULT_GLYPH_CACHE_CAPACITY`], so
+    /// long-running apps can tune how many rasterized glyphs are kept
+    /// around before the LRU evicts the least-recently-used entry.
+    pub fn from_bytes_with_cache_capacity(
+        bytes: impl Into<Vec<u8>>,
+        pixel_size: u32,
+        cache_capacity: usize,
+    ) -> Result<Self, &'static str> {
+        let bytes = bytes.into();
+        let shared_bytes: Arc<[u8]> = Arc::from(bytes.clone());
+
+        rusttype::Font::try_from_vec(bytes)
             .map(|font| Font {
                 inner: font,
+                bytes: shared_bytes,
                 pixel_size,
+                id: NEXT_FONT_ID.fetch_add(1, Ordering::Relaxed),
+                cache: Rc::new(RefCell::new(GlyphCache::new(cache_capacity))),
                 _c: PhantomData::default(),
             })
             .ok_or("Could not load font from bytes")
     }
 
+    /// Parses this font's OpenType tables with `ttf-parser`, for reading
+    /// data `rusttype` doesn't expose (such as color bitmap glyphs).
+    ///
+    /// Returns `None` if the underlying bytes aren't a font `ttf-parser`
+    /// understands; since `rusttype` already accepted them in
+    /// [`Font::from_bytes`], this should only happen for formats one parser
+    /// supports and the other doesn't.
+    fn face(&self) -> Option<ttf_parser::Face<'_>> {
+        ttf_parser::Face::parse(&self.bytes, 0).ok()
+    }
+
+    /// Returns `g`'s entry in this font's glyph cache, rasterizing `g` at
+    /// `font_size` on a cache miss, or `None` if `g` has no pixel bounding
+    /// box.
+    pub(crate) fn cached_glyph(
+        &self,
+        g: &rusttype::PositionedGlyph,
+        font_size: f32,
+    ) -> Option<AtlasEntry> {
+        let bbox = g.pixel_bounding_box()?;
+        let width = (bbox.max.x - bbox.min.x) as u32;
+        let height = (bbox.max.y - bbox.min.y) as u32;
+        let key = GlyphKey::new(self.id, g.id(), font_size);
+
+        let mut cache = self.cache.borrow_mut();
+        if let Some(entry) = cache.get(&key) {
+            return Some(entry);
+        }
+
+        let coverage = rasterize_glyph(g, bbox, width, height);
+        Some(cache.insert(
+            key,
+            &coverage,
+            width,
+            height,
+            bbox.min.x,
+            bbox.min.y,
+            g.unpositioned().h_metrics().advance_width,
+        ))
+    }
+
+    pub(crate) fn cache(&self) -> Ref<GlyphCache> {
+        self.cache.borrow()
+    }
+
     pub fn measure_text(&self, text: &str, size: f64) -> (f64, f64) {
         let scale = rusttype::Scale::uniform(size as f32);
         let v_metrics = self.inner.v_metrics(scale);
@@ -251,6 +616,11 @@ impl<C: PixelColor> Font<C> {
         paint: &Paint,
         position: (f64, f64),
     ) {
+        let color = match paint.shader {
+            Shader::SolidColor(color) => color,
+            _ => return,
+        };
+
         let scale = rusttype::Scale::uniform(font_size as f32);
 
         // The origin of a line of text is at the baseline (roughly where non-descending letters sit).
@@ -263,28 +633,209 @@ impl<C: PixelColor> Font<C> {
         let glyphs: Vec<rusttype::PositionedGlyph> =
             self.inner.layout(text, scale, offset).collect();
 
-        let mut glyph_tracer = GlyphTracer {
-            path_builder: PathBuilder::new(),
-            position: rusttype::point(0.0, 0.0),
-        };
+        let face = self.face();
+        let gamma_lut = build_gamma_lut(DEFAULT_GAMMA, DEFAULT_CONTRAST);
+
         for g in glyphs.iter() {
-            let mut gpos = match g.pixel_bounding_box() {
-                Some(bbox) => rusttype::point(bbox.min.x as f32, bbox.min.y as f32),
-                None => {
-                    continue;
-                }
+            let bbox = match g.pixel_bounding_box() {
+                Some(bbox) => bbox,
+                None => continue,
             };
-            gpos.x += position.0 as f32;
-            gpos.y += position.1 as f32;
-            glyph_tracer.position = gpos;
-            g.build_outline(&mut glyph_tracer);
+
+            let dest_x = position.0 as i32 + bbox.min.x;
+            let dest_y = position.1 as i32 + bbox.min.y;
+
+            let glyph_id = ttf_parser::GlyphId(g.id().0 as u16);
+            if let Some(image) = face
+                .as_ref()
+                .and_then(|face| color_glyph_image(face, glyph_id, color))
+            {
+                blit_color_glyph(pix_map, &image, font_size as f32, dest_x, dest_y);
+                continue;
+            }
+
+            if let Some(entry) = self.cached_glyph(g, font_size as f32) {
+                blit_coverage_into_pixmap(
+                    &self.cache(),
+                    &entry,
+                    pix_map,
+                    dest_x,
+                    dest_y,
+                    color,
+                    &gamma_lut,
+                );
+            }
+        }
+    }
+}
+
+/// Builds the gamma-correction lookup table used to remap glyph coverage
+/// before it is composited, so anti-aliased edges don't look thin and
+/// muddy: `lut[a] = 255 * (a/255 * contrast)^(1/gamma)`.
+fn build_gamma_lut(gamma: f32, contrast: f32) -> [u8; 256] {
+    let mut lut = [0u8; 256];
+
+    for (coverage, entry) in lut.iter_mut().enumerate() {
+        let boosted = (coverage as f32 / 255.0 * contrast).clamp(0.0, 1.0);
+        *entry = (255.0 * boosted.powf(1.0 / gamma)).round() as u8;
+    }
+
+    lut
+}
+
+/// Rasterizes a single glyph's outline into a `width x height` coverage
+/// buffer, anchored at the glyph's pixel bounding box.
+fn rasterize_glyph(
+    g: &rusttype::PositionedGlyph,
+    bbox: rusttype::Rect<i32>,
+    width: u32,
+    height: u32,
+) -> Vec<u8> {
+    if width == 0 || height == 0 {
+        return Vec::new();
+    }
+
+    let mut tracer = GlyphTracer {
+        path_builder: PathBuilder::new(),
+        position: rusttype::point(-(bbox.min.x as f32), -(bbox.min.y as f32)),
+    };
+    g.build_outline(&mut tracer);
+
+    match tracer.path_builder.finish() {
+        Some(path) => {
+            let mut mask =
+                Mask::new(width, height).expect("glyph coverage mask must have a non-zero size");
+            mask.fill_path(&path, FillRule::Winding, true, Transform::identity());
+            mask.data().to_vec()
         }
-        if let Some(path) = glyph_tracer.path_builder.finish() {
-            pix_map.fill_path(&path, paint, FillRule::Winding, Transform::identity(), None);
+        None => vec![0; (width * height) as usize],
+    }
+}
+
+/// Scales a decoded color glyph bitmap to `font_size` and composites it
+/// into `pix_map` at `(dest_x, dest_y)`.
+fn blit_color_glyph(
+    pix_map: &mut Pixmap,
+    image: &ColorGlyphImage,
+    font_size: f32,
+    dest_x: i32,
+    dest_y: i32,
+) {
+    let scale = font_size / image.pixels_per_em as f32;
+
+    // `draw_pixmap` applies `transform` after its own `(x, y)` offset, so
+    // the destination goes into the transform to keep it from being scaled.
+    pix_map.draw_pixmap(
+        0,
+        0,
+        image.pixmap.as_ref(),
+        &tiny_skia::PixmapPaint::default(),
+        Transform::from_row(scale, 0.0, 0.0, scale, dest_x as f32, dest_y as f32),
+        None,
+    );
+}
+
+/// Blends a cached glyph's coverage bitmap into `pix_map` at `(dest_x,
+/// dest_y)`, remapping coverage through `gamma_lut` and compositing `color`
+/// using simple source-over blending.
+fn blit_coverage_into_pixmap(
+    cache: &GlyphCache,
+    entry: &AtlasEntry,
+    pix_map: &mut Pixmap,
+    dest_x: i32,
+    dest_y: i32,
+    color: Color,
+    gamma_lut: &[u8; 256],
+) {
+    let atlas = cache.atlas();
+    let atlas_width = atlas.width() as usize;
+    let atlas_data = atlas.data();
+
+    let pm_width = pix_map.width() as i32;
+    let pm_height = pix_map.height() as i32;
+
+    let cr = (color.red() * 255.0).round() as u32;
+    let cg = (color.green() * 255.0).round() as u32;
+    let cb = (color.blue() * 255.0).round() as u32;
+    let ca = (color.alpha() * 255.0).round() as u32;
+
+    let x0 = entry.atlas_rect.left() as usize;
+    let y0 = entry.atlas_rect.top() as usize;
+    let width = entry.atlas_rect.width() as usize;
+    let height = entry.atlas_rect.height() as usize;
+
+    let data = pix_map.data_mut();
+
+    for row in 0..height {
+        let py = dest_y + row as i32;
+        if py < 0 || py >= pm_height {
+            continue;
+        }
+
+        for col in 0..width {
+            let px = dest_x + col as i32;
+            if px < 0 || px >= pm_width {
+                continue;
+            }
+
+            let coverage = gamma_lut[atlas_data[(y0 + row) * atlas_width + x0 + col] as usize] as u32;
+            let alpha = coverage * ca / 255;
+            if alpha == 0 {
+                continue;
+            }
+
+            let index = (py as usize * pm_width as usize + px as usize) * 4;
+            let inv = 255 - alpha;
+            data[index] = ((cr * alpha + data[index] as u32 * inv) / 255) as u8;
+            data[index + 1] = ((cg * alpha + data[index + 1] as u32 * inv) / 255) as u8;
+            data[index + 2] = ((cb * alpha + data[index + 2] as u32 * inv) / 255) as u8;
+            data[index + 3] = 255;
         }
     }
 }
 
+/// Draws a cached glyph's coverage bitmap into a generic embedded-graphics
+/// `DrawTarget`, remapping coverage through `gamma_lut` and thresholding it
+/// into opaque `color` pixels since `PixelColor` has no general
+/// alpha-blending concept.
+fn draw_glyph_coverage<D, C>(
+    cache: &GlyphCache,
+    entry: &AtlasEntry,
+    glyph_origin: Point,
+    color: C,
+    gamma_lut: &[u8; 256],
+    target: &mut D,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = C>,
+    C: PixelColor,
+{
+    let atlas = cache.atlas();
+    let atlas_width = atlas.width() as usize;
+    let atlas_data = atlas.data();
+
+    let x0 = entry.atlas_rect.left() as usize;
+    let y0 = entry.atlas_rect.top() as usize;
+    let width = entry.atlas_rect.width() as usize;
+    let height = entry.atlas_rect.height() as usize;
+
+    let pixels = (0..height).flat_map(move |row| {
+        (0..width).filter_map(move |col| {
+            let coverage = gamma_lut[atlas_data[(y0 + row) * atlas_width + x0 + col] as usize];
+            if coverage > 127 {
+                Some(Pixel(
+                    glyph_origin + Point::new(col as i32, row as i32),
+                    color,
+                ))
+            } else {
+                None
+            }
+        })
+    });
+
+    target.draw_iter(pixels)
+}
+
 impl<C: PixelColor> TextRenderer for FontTextStyle<C> {
     type Color = C;
 
@@ -292,44 +843,17 @@ impl<C: PixelColor> TextRenderer for FontTextStyle<C> {
     where
         D: DrawTarget<Color = Self::Color>,
     {
-        let mut p = position;
-
-        let scale = rusttype::Scale::uniform(self.font_size as f32);
+        let lines = self.layout(text);
+        let line_height = TextRenderer::line_height(self) as i32;
+        let gamma_lut = self.gamma_lut();
 
-        let v_metrics = self.font.inner.v_metrics(scale);
-        let offset = rusttype::point(0.0, v_metrics.ascent);
-
-        let glyphs: Vec<rusttype::PositionedGlyph> =
-            self.font.inner.layout(text, scale, offset).collect();
-
-        let mut glyph_tracer = GlyphTracer {
-            path_builder: PathBuilder::new(),
-            position: rusttype::point(0.0, 0.0),
-        };
-
-        let mut width = 0;
+        let mut p = position;
 
-        for g in glyphs.iter() {
-            let mut gpos = match g.pixel_bounding_box() {
-                Some(bbox) => rusttype::point(bbox.min.x as f32, bbox.min.y as f32),
-                None => {
-                    continue;
-                }
-            };
-            gpos.x += position.x as f32;
-            gpos.y += position.y as f32;
-            glyph_tracer.position = gpos;
-            g.build_outline(&mut glyph_tracer);
-
-            p = Point::new(gpos.x as i32, p.y);
-            width +=
-                (g.position().x as f32 + g.unpositioned().h_metrics().advance_width).ceil() as u32;
+        for (i, line) in lines.iter().enumerate() {
+            let line_position = Point::new(position.x, position.y + i as i32 * line_height);
+            p = self.draw_line(&line.text, line_position, &gamma_lut, target)?;
         }
 
-        self.draw_background(width, position, target)?;
-        self.draw_strikethrough(width, position, target)?;
-        self.draw_underline(width, position, target)?;
-
         Ok(p)
     }
 
@@ -342,38 +866,86 @@ impl<C: PixelColor> TextRenderer for FontTextStyle<C> {
     where
         D: DrawTarget<Color = Self::Color>,
     {
-        todo!()
+        self.draw_background(width, position, target)?;
+        self.draw_strikethrough(width, position, target)?;
+        self.draw_underline(width, position, target)?;
+
+        Ok(position + Point::new(width as i32, 0))
     }
 
     fn measure_string(&self, text: &str, position: Point) -> TextMetrics {
-        let scale = rusttype::Scale::uniform(self.font_size as f32);
-        let v_metrics = self.font.inner.v_metrics(scale);
-        let offset = rusttype::point(0.0, v_metrics.ascent);
-
-        let glyphs: Vec<rusttype::PositionedGlyph> =
-            self.font.inner.layout(text, scale, offset).collect();
+        let lines = self.layout(text);
+        let line_height = TextRenderer::line_height(self);
 
-        let width = glyphs
+        let width = lines
             .iter()
-            .rev()
-            .map(|g| g.position().x as f32 + g.unpositioned().h_metrics().advance_width)
-            .next()
-            .unwrap_or(0.0)
-            .ceil() as f64;
+            .map(|line| self.measure_advance(&line.text).ceil() as u32)
+            .max()
+            .unwrap_or(0);
 
-        let size = Size::new(width as u32, self.font_size);
+        let height = (lines.len() as u32).max(1) * line_height;
+        let size = Size::new(width, height);
 
         TextMetrics {
             bounding_box: Rectangle::new(position, size),
-            next_position: position + size.x_axis(),
+            next_position: position
+                + Point::new(width as i32, (lines.len() as i32 - 1).max(0) * line_height as i32),
         }
     }
 
-    fn vertical_offset(&self, position: Point, _vertical_alignment: VerticalAlignment) -> Point {
-        position
+    fn vertical_offset(&self, position: Point, vertical_alignment: VerticalAlignment) -> Point {
+        let v_metrics = self.font.inner.v_metrics(self.scale());
+
+        let y_offset = match vertical_alignment {
+            VerticalAlignment::Top => v_metrics.ascent,
+            VerticalAlignment::Bottom => -v_metrics.descent,
+            VerticalAlignment::Middle => (v_metrics.ascent + v_metrics.descent) / 2.0,
+            VerticalAlignment::Alphabetic => 0.0,
+        };
+
+        Point::new(position.x, position.y + y_offset.round() as i32)
     }
 
     fn line_height(&self) -> u32 {
-        self.font_size
+        let v_metrics = self.font.inner.v_metrics(self.scale());
+
+        (v_metrics.ascent - v_metrics.descent + v_metrics.line_gap).round() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gamma_lut_maps_endpoints_unchanged() {
+        let lut = build_gamma_lut(1.8, 1.0);
+
+        assert_eq!(lut[0], 0);
+        assert_eq!(lut[255], 255);
+    }
+
+    #[test]
+    fn gamma_lut_of_one_is_the_identity() {
+        let lut = build_gamma_lut(1.0, 1.0);
+
+        for coverage in 0..=255u8 {
+            assert_eq!(lut[coverage as usize], coverage);
+        }
+    }
+
+    #[test]
+    fn gamma_above_one_boosts_partial_coverage() {
+        let lut = build_gamma_lut(1.8, 1.0);
+
+        assert!(lut[128] > 128);
+    }
+
+    #[test]
+    fn contrast_scales_coverage_before_gamma() {
+        let identity = build_gamma_lut(1.0, 1.0);
+        let boosted = build_gamma_lut(1.0, 2.0);
+
+        assert!(boosted[64] > identity[64]);
     }
 }
@@ -0,0 +1,133 @@
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Point, Size},
+    pixelcolor::PixelColor,
+    Pixel,
+};
+
+/// How a [`FontText`](crate::font::FontText) is rotated; the rotated text
+/// occupies a box whose top-left corner is the text's origin.
+///
+/// Axis-aligned 90° multiples are exact integer pixel transforms: the text
+/// is laid out normally into an off-screen buffer, then every pixel's
+/// position is remapped before being drawn into the real target, with no
+/// resampling. Arbitrary-angle rotation isn't implemented yet — it would
+/// need to rotate the rasterized coverage bitmap itself rather than just
+/// permuting pixel coordinates.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum Rotation {
+    /// No rotation. The default.
+    #[default]
+    None,
+    /// Rotated 90° clockwise.
+    Rotate90,
+    /// Rotated 180°.
+    Rotate180,
+    /// Rotated 270° clockwise (90° counter-clockwise).
+    Rotate270,
+}
+
+impl Rotation {
+    /// Maps a point in an unrotated `bounds`-sized local coordinate space
+    /// (top-left at the origin) to its rotated position, still relative to
+    /// the origin.
+    pub(crate) fn transform_point(self, p: Point, bounds: Size) -> Point {
+        let w = bounds.width as i32;
+        let h = bounds.height as i32;
+
+        match self {
+            Rotation::None => p,
+            Rotation::Rotate90 => Point::new(h - 1 - p.y, p.x),
+            Rotation::Rotate180 => Point::new(w - 1 - p.x, h - 1 - p.y),
+            Rotation::Rotate270 => Point::new(p.y, w - 1 - p.x),
+        }
+    }
+
+    /// The rotated bounding size for an unrotated `bounds`.
+    pub(crate) fn transform_size(self, bounds: Size) -> Size {
+        match self {
+            Rotation::None | Rotation::Rotate180 => bounds,
+            Rotation::Rotate90 | Rotation::Rotate270 => Size::new(bounds.height, bounds.width),
+        }
+    }
+}
+
+/// A scratch `DrawTarget` that just records drawn pixels in local,
+/// unrotated coordinates, so they can be remapped through a [`Rotation`]
+/// before being drawn into the real target.
+pub(crate) struct PixelBuffer<C: PixelColor> {
+    size: Size,
+    pixels: Vec<Pixel<C>>,
+}
+
+impl<C: PixelColor> PixelBuffer<C> {
+    pub(crate) fn new(size: Size) -> Self {
+        Self {
+            size,
+            pixels: Vec::new(),
+        }
+    }
+
+    pub(crate) fn into_pixels(self) -> Vec<Pixel<C>> {
+        self.pixels
+    }
+}
+
+impl<C: PixelColor> OriginDimensions for PixelBuffer<C> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl<C: PixelColor> DrawTarget for PixelBuffer<C> {
+    type Color = C;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<C>>,
+    {
+        self.pixels.extend(pixels);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn none_leaves_points_unchanged() {
+        let bounds = Size::new(10, 20);
+        assert_eq!(Rotation::None.transform_point(Point::new(3, 4), bounds), Point::new(3, 4));
+        assert_eq!(Rotation::None.transform_size(bounds), bounds);
+    }
+
+    #[test]
+    fn rotate90_maps_corners() {
+        let bounds = Size::new(10, 20);
+
+        assert_eq!(Rotation::Rotate90.transform_point(Point::new(0, 0), bounds), Point::new(19, 0));
+        assert_eq!(Rotation::Rotate90.transform_point(Point::new(9, 19), bounds), Point::new(0, 9));
+        assert_eq!(Rotation::Rotate90.transform_size(bounds), Size::new(20, 10));
+    }
+
+    #[test]
+    fn rotate180_maps_corners() {
+        let bounds = Size::new(10, 20);
+
+        assert_eq!(Rotation::Rotate180.transform_point(Point::new(0, 0), bounds), Point::new(9, 19));
+        assert_eq!(Rotation::Rotate180.transform_point(Point::new(9, 19), bounds), Point::new(0, 0));
+        assert_eq!(Rotation::Rotate180.transform_size(bounds), bounds);
+    }
+
+    #[test]
+    fn rotate270_maps_corners() {
+        let bounds = Size::new(10, 20);
+
+        assert_eq!(Rotation::Rotate270.transform_point(Point::new(0, 0), bounds), Point::new(0, 9));
+        assert_eq!(Rotation::Rotate270.transform_point(Point::new(9, 19), bounds), Point::new(19, 0));
+        assert_eq!(Rotation::Rotate270.transform_size(bounds), Size::new(20, 10));
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::marker::PhantomData;
 
 use tiny_skia::*;
@@ -14,6 +15,8 @@ use embedded_graphics_core::{
 
 pub mod font;
 
+use font::{Font, FontSource, FontStyle, FontTextStyle, FontTextStyleBuilder};
+
 /// This display is based on raqote's `DrawTarget` and is used as draw target for the embedded graphics crate.
 ///
 /// # Example
@@ -39,6 +42,9 @@ where
 {
     pix_map: Pixmap,
     size: Size,
+    /// Fonts registered via [`Self::register_font`], looked up by family
+    /// name when resolving a [`FontStyle`] to a drawable [`FontTextStyle`].
+    fonts: HashMap<String, Font<C>>,
     _pixel_color: PhantomData<C>,
 }
 
@@ -144,11 +150,70 @@ where
         Ok(TinySkiaDisplay {
             pix_map: Pixmap::new(width, height).ok_or("Cannot create tiny-skia Pixmap")?,
             size: Size::new(width, height),
-            // fonts: HashMap::new(),
+            fonts: HashMap::new(),
             _pixel_color: PhantomData::default(),
         })
     }
 
+    /// Registers a font under `name`, so a [`FontStyle`] naming that family
+    /// can be resolved to a drawable [`FontTextStyle`] via
+    /// [`Self::text_style`].
+    pub fn register_font(
+        &mut self,
+        name: impl Into<String>,
+        bytes: impl Into<Vec<u8>>,
+        pixel_size: u32,
+    ) -> Result<(), &'static str> {
+        let font = Font::from_bytes(bytes, pixel_size)?;
+        self.fonts.insert(name.into(), font);
+
+        Ok(())
+    }
+
+    /// Like [`Self::register_font`], but with an explicit glyph
+    /// rasterization cache capacity, so long-running apps with many
+    /// distinct glyphs (or many font/size combinations) can tune how much
+    /// memory the cache is allowed to hold before it evicts the
+    /// least-recently-used glyph.
+    pub fn register_font_with_cache_capacity(
+        &mut self,
+        name: impl Into<String>,
+        bytes: impl Into<Vec<u8>>,
+        pixel_size: u32,
+        cache_capacity: usize,
+    ) -> Result<(), &'static str> {
+        let font = Font::from_bytes_with_cache_capacity(bytes, pixel_size, cache_capacity)?;
+        self.fonts.insert(name.into(), font);
+
+        Ok(())
+    }
+
+    /// Returns the font registered under `name`, if any.
+    pub fn font(&self, name: &str) -> Option<&Font<C>> {
+        self.fonts.get(name)
+    }
+
+    /// Resolves a [`FontStyle`] to a drawable [`FontTextStyle`] by looking
+    /// up its [`FontSource`] in this display's font registry, or loading it
+    /// directly if it carries inline font bytes. Returns `None` if the
+    /// style names a family that isn't registered.
+    pub fn text_style(&self, style: &FontStyle<C>) -> Option<FontTextStyle<C>> {
+        let font = match &style.font_source {
+            FontSource::Registered(name) => self.font(name)?.clone(),
+            FontSource::Inline(bytes) => Font::from_bytes(bytes.to_vec(), style.pixel_size).ok()?,
+        };
+
+        let mut builder = FontTextStyleBuilder::new(font).font_size(style.pixel_size);
+        if let Some(text_color) = style.text_color {
+            builder = builder.text_color(text_color);
+        }
+        if let Some(background_color) = style.background_color {
+            builder = builder.background_color(background_color);
+        }
+
+        Some(builder.build())
+    }
+
     /// Returns a reference to the underlying pixel data.
     pub fn data(&self) -> &[u8] {
         self.pix_map.data()
@@ -202,3 +267,11 @@ fn convert_color_to_paint<'a, C: PixelColor + Into<Rgb888>>(color: C) -> Paint<'
     paint.set_color_rgba8(r, g, b, a);
     paint
 }
+
+/// Converts an embedded-graphics pixel color to a plain tiny-skia [`Color`],
+/// for code that composites directly against a `Pixmap` (e.g. color glyph
+/// blitting) rather than going through a [`Paint`].
+pub(crate) fn to_skia_color<C: PixelColor + Into<Rgb888>>(color: C) -> Color {
+    let rgb: Rgb888 = color.into();
+    Color::from_rgba8(rgb.r(), rgb.g(), rgb.b(), 255)
+}